@@ -1,6 +1,6 @@
 use anyhow::Result;
-use http::Request;
-use ws_sdk::api::api_call;
+use ws_sdk::api::system::{ReadTxRequest, SystemRequest};
+use ws_sdk::blockchain::Chain;
 use ws_sdk::log::log_info;
 use ws_sdk::stream::get_data;
 
@@ -12,29 +12,22 @@ pub extern "C" fn start(rid: i32) -> i32 {
     };
 }
 
-fn handle(rid: i32) -> Result<()> {
-    let json_str = r#"
-    {
-        "chainName":"iotex-testnet",
-        "hash":"fcaf377ff3cc785d60c58de7e121d6a2e79e1c58c189ea8641f3ea61f7605285"
-    }
-    "#;
+fn handle(_rid: i32) -> Result<()> {
+    let chain: Chain = "iotex-testnet".parse()?;
+    let hash = "fcaf377ff3cc785d60c58de7e121d6a2e79e1c58c189ea8641f3ea61f7605285".parse()?;
 
-    let request = Request::builder()
-        .method("GET")
-        .uri("/system/read_tx")
-        .header("EventType", "result")
-        .body(json_str.as_bytes().to_vec())?;
-
-    let ret = api_call(request)?;
-    log_info(&format!("ret: {}", ret))?;
+    // the result is routed to `handle_result` by the event type `result`
+    ReadTxRequest::new(&chain, hash).send_routed("result")?;
     Ok(())
 }
 
 #[no_mangle]
 pub extern "C" fn handle_result(rid: i32) -> i32 {
     log_info(&format!("start rid: {}", rid));
-    let data_str = String::from_utf8(get_data(rid as _).unwrap()).unwrap();
-    log_info(&format!("get resource {}: `{}`", rid, data_str));
+    match get_data(rid as _).and_then(|data| ReadTxRequest::parse_result(&data)) {
+        Ok(Some(tx)) => log_info(&format!("get tx {}: {:?}", rid, tx)),
+        Ok(None) => log_info(&format!("tx not found: {}", rid)),
+        Err(e) => log_info(&format!("fail to read tx {}: {}", rid, e)),
+    };
     0
 }
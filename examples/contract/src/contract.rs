@@ -1,44 +1,3 @@
-use ethabi::Contract;
-use lazy_static::lazy_static;
-use serde_json;
+use ws_sdk::blockchain::abi::abigen;
 
-lazy_static! {
-    pub static ref SET_GET: Contract = serde_json::from_str(ABI).unwrap();
-}
-
-const ABI: &str = r#"
-[
-	{
-		"inputs": [],
-		"name": "get",
-		"outputs": [
-			{
-				"internalType": "uint256",
-				"name": "",
-				"type": "uint256"
-			}
-		],
-		"stateMutability": "view",
-		"type": "function"
-	},
-	{
-		"inputs": [
-			{
-				"internalType": "uint256",
-				"name": "x",
-				"type": "uint256"
-			}
-		],
-		"name": "set",
-		"outputs": [
-			{
-				"internalType": "uint256",
-				"name": "",
-				"type": "uint256"
-			}
-		],
-		"stateMutability": "nonpayable",
-		"type": "function"
-	}
-]
-"#;
+abigen!(SetGet, "abi/set_get.json");
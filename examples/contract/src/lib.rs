@@ -1,6 +1,5 @@
 use anyhow::Result;
-use hex;
-use ws_sdk::blockchain::*;
+use ws_sdk::blockchain::{types::U256, Chain};
 use ws_sdk::log::log_info;
 
 mod contract;
@@ -16,19 +15,15 @@ pub extern "C" fn start(rid: i32) -> i32 {
 }
 
 fn handle(rid: i32) -> Result<()> {
+    let set_get = contract::SetGet::new(Chain::IotexTestnet.id(), CONTRACT_ADDR.parse()?);
+
     // get current number on the contract
-    let encoded_get = contract::SET_GET.function("get")?.encode_input(&[])?;
-    let data_get = hex::encode(encoded_get);
-    let ret = call_contract(4690, CONTRACT_ADDR, &data_get)?;
-    log_info(&format!("get current num: {:?}", ret))?;
+    let current = set_get.get()?;
+    log_info(&format!("get current num: {}", current))?;
 
     // set the number on the contract to `rid % 100`
     let new_num = rid % 100;
-    let encoded_set = contract::SET_GET
-        .function("set")?
-        .encode_input(&[ethabi::Token::Uint(ethabi::Uint::from(new_num))])?;
-    let data_set = hex::encode(encoded_set);
-    let hash = send_tx(4690, CONTRACT_ADDR, "0", &data_set)?;
+    let hash = set_get.set(U256::try_from(new_num)?)?;
 
     log_info(&format!("set new num: {}, tx hash: {}", new_num, hash))?;
 
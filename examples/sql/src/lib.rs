@@ -1,7 +1,14 @@
 use anyhow::Result;
+use serde::Deserialize;
 use ws_sdk::database::sql::*;
 use ws_sdk::log::log_info;
 
+#[derive(Deserialize)]
+struct Record {
+    #[serde(rename = "ID")]
+    id: i32,
+}
+
 #[no_mangle]
 pub extern "C" fn start(rid: i32) -> i32 {
     match handle(rid) {
@@ -12,7 +19,9 @@ pub extern "C" fn start(rid: i32) -> i32 {
 
 fn handle(rid: i32) -> Result<()> {
     execute("INSERT INTO table1 (ID) VALUES (?);", &[&rid])?;
-    let ret = query("SELECT * FROM table1;", &[])?;
-    log_info(&String::from_utf8(ret)?)?;
+    let records: Vec<Record> = query_as("SELECT * FROM table1;", &[])?;
+    for record in records {
+        log_info(&format!("get record: {}", record.id))?;
+    }
     Ok(())
 }
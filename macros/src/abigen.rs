@@ -0,0 +1,381 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Ident, LitStr, Result, Token};
+
+pub struct Input {
+    name: Ident,
+    source: LitStr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let source = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Input { name, source })
+    }
+}
+
+struct Param {
+    name: String,
+    // the canonical type, e.g. `(address,uint256)[]`
+    canonical: String,
+    rust: TokenStream,
+}
+
+struct Function {
+    name: String,
+    inputs: Vec<Param>,
+    outputs: Vec<Param>,
+    view: bool,
+    payable: bool,
+}
+
+pub fn expand(input: Input) -> Result<TokenStream> {
+    let span = input.source.span();
+    let source = input.source.value();
+    let (json, tracked) = if source.trim_start().starts_with('[') {
+        (source, None)
+    } else {
+        let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+        let path = PathBuf::from(dir).join(&source);
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| Error::new(span, format!("fail to read `{}`: {}", path.display(), e)))?;
+        (json, Some(path.display().to_string()))
+    };
+    let abi: Value = serde_json::from_str(&json)
+        .map_err(|e| Error::new(span, format!("invalid json abi: {}", e)))?;
+    // a compiled artifact holds the abi in the `abi` field
+    let entries = match abi.get("abi").unwrap_or(&abi) {
+        Value::Array(entries) => entries,
+        _ => return Err(Error::new(span, "expected a json abi array")),
+    };
+
+    let mut functions = Vec::new();
+    for entry in entries {
+        if entry
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("function")
+            != "function"
+        {
+            continue;
+        }
+        functions.push(parse_function(entry).map_err(|e| Error::new(span, e))?);
+    }
+
+    let name = &input.name;
+    let mut seen = HashMap::new();
+    let bases: Vec<String> = functions
+        .iter()
+        .map(|f| {
+            let count = seen.entry(f.name.as_str()).or_insert(0);
+            let base = match *count {
+                0 => to_snake_case(&f.name),
+                n => format!("{}_{}", to_snake_case(&f.name), n),
+            };
+            *count += 1;
+            base
+        })
+        .collect();
+    // not to shadow the methods of the struct or the `encode_*` helpers, e.g.
+    // of `encodeX` by the one of `x`
+    let mut taken: HashSet<String> = ["new", "chain_id", "address"]
+        .iter()
+        .map(|s| s.to_string())
+        .chain(bases.iter().map(|b| format!("encode_{}", b)))
+        .collect();
+    let mut generated = HashSet::new();
+    let mut methods = Vec::with_capacity(functions.len());
+    for (f, mut base) in functions.iter().zip(bases) {
+        while taken.contains(&base) {
+            base.push('_');
+        }
+        taken.insert(base.clone());
+        for method in [base.clone(), format!("encode_{}", base)] {
+            if !generated.insert(method.clone()) {
+                let msg = format!("the method `{}` of `{}` is generated twice", method, f.name);
+                return Err(Error::new(span, msg));
+            }
+        }
+        methods.push(expand_function(f, &base));
+    }
+    let tracked = tracked.map(|path| {
+        quote!(
+            const _: &str = include_str!(#path);
+        )
+    });
+    let doc = format!("Bindings of the contract `{}`.", name);
+
+    Ok(quote! {
+        #tracked
+
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct #name {
+            chain_id: u32,
+            address: ::ws_sdk::blockchain::types::Address,
+        }
+
+        impl #name {
+            /// Binds the contract at `address` on the chain `chain_id`.
+            pub fn new(chain_id: u32, address: ::ws_sdk::blockchain::types::Address) -> Self {
+                Self { chain_id, address }
+            }
+
+            /// Returns the chain id of the contract.
+            pub fn chain_id(&self) -> u32 {
+                self.chain_id
+            }
+
+            /// Returns the address of the contract.
+            pub fn address(&self) -> ::ws_sdk::blockchain::types::Address {
+                self.address
+            }
+
+            #(#methods)*
+        }
+    })
+}
+
+fn parse_function(entry: &Value) -> std::result::Result<Function, String> {
+    let name = entry
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("a function without name")?
+        .to_string();
+    let params = |key: &str| -> std::result::Result<Vec<Param>, String> {
+        match entry.get(key) {
+            Some(Value::Array(ps)) => ps
+                .iter()
+                .enumerate()
+                .map(|(i, p)| parse_param(p, i))
+                .collect::<std::result::Result<_, _>>()
+                .map_err(|e| format!("{} of `{}`: {}", key, name, e)),
+            _ => Ok(vec![]),
+        }
+    };
+    let inputs = params("inputs")?;
+    let outputs = params("outputs")?;
+    let mutability = entry.get("stateMutability").and_then(Value::as_str);
+    let constant = entry.get("constant").and_then(Value::as_bool) == Some(true);
+    let payable = entry.get("payable").and_then(Value::as_bool) == Some(true);
+    Ok(Function {
+        name,
+        inputs,
+        outputs,
+        view: matches!(mutability, Some("view") | Some("pure")) || constant,
+        payable: mutability == Some("payable") || payable,
+    })
+}
+
+fn parse_param(p: &Value, index: usize) -> std::result::Result<Param, String> {
+    let name = p.get("name").and_then(Value::as_str).unwrap_or_default();
+    let ty = p
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or("a parameter without type")?;
+    let components = match p.get("components") {
+        Some(Value::Array(cs)) => cs
+            .iter()
+            .enumerate()
+            .map(|(i, c)| parse_param(c, i))
+            .collect::<std::result::Result<_, _>>()?,
+        _ => vec![],
+    };
+    let (canonical, rust) = resolve(ty, &components)?;
+    Ok(Param {
+        name: if name.is_empty() {
+            format!("arg{}", index)
+        } else {
+            to_snake_case(name)
+        },
+        canonical,
+        rust,
+    })
+}
+
+fn resolve(ty: &str, components: &[Param]) -> std::result::Result<(String, TokenStream), String> {
+    if let Some(inner) = ty.strip_suffix(']') {
+        let open = inner.rfind('[').ok_or(format!("invalid type `{}`", ty))?;
+        let (canonical, rust) = resolve(&inner[..open], components)?;
+        let dim = &inner[open + 1..];
+        let canonical = format!("{}[{}]", canonical, dim);
+        return Ok(if dim.is_empty() {
+            (canonical, quote!(::std::vec::Vec<#rust>))
+        } else {
+            let n: usize = dim.parse().map_err(|_| format!("invalid type `{}`", ty))?;
+            (canonical, quote!([#rust; #n]))
+        });
+    }
+    let abi = quote!(::ws_sdk::blockchain::abi);
+    let rust = match ty {
+        "tuple" => {
+            let canonical: Vec<&str> = components.iter().map(|c| c.canonical.as_str()).collect();
+            let rust = components.iter().map(|c| &c.rust);
+            return Ok((format!("({})", canonical.join(",")), quote!((#(#rust,)*))));
+        }
+        "address" => quote!(#abi::Address),
+        "bool" => quote!(bool),
+        "string" => quote!(::std::string::String),
+        "bytes" => quote!(::std::vec::Vec<u8>),
+        "uint" | "int" => return resolve(&format!("{}256", ty), components),
+        "function" => return resolve("bytes24", components),
+        _ => {
+            if let Some(n) = ty.strip_prefix("bytes") {
+                let n: usize = n.parse().map_err(|_| format!("invalid type `{}`", ty))?;
+                quote!([u8; #n])
+            } else if let Some(bits) = ty.strip_prefix("uint") {
+                match bits.parse::<usize>() {
+                    Ok(n) if n <= 8 => quote!(u8),
+                    Ok(n) if n <= 16 => quote!(u16),
+                    Ok(n) if n <= 32 => quote!(u32),
+                    Ok(n) if n <= 64 => quote!(u64),
+                    Ok(n) if n <= 128 => quote!(u128),
+                    Ok(n) if n <= 256 => quote!(#abi::U256),
+                    _ => return Err(format!("invalid type `{}`", ty)),
+                }
+            } else if let Some(bits) = ty.strip_prefix("int") {
+                match bits.parse::<usize>() {
+                    Ok(n) if n <= 8 => quote!(i8),
+                    Ok(n) if n <= 16 => quote!(i16),
+                    Ok(n) if n <= 32 => quote!(i32),
+                    Ok(n) if n <= 64 => quote!(i64),
+                    Ok(n) if n <= 128 => quote!(i128),
+                    Ok(n) if n <= 256 => quote!(#abi::U256),
+                    _ => return Err(format!("invalid type `{}`", ty)),
+                }
+            } else {
+                return Err(format!("unsupported type `{}`", ty));
+            }
+        }
+    };
+    Ok((ty.to_string(), rust))
+}
+
+fn expand_function(f: &Function, base: &str) -> TokenStream {
+    let abi = quote!(::ws_sdk::blockchain::abi);
+    let anyhow = quote!(::ws_sdk::__private::anyhow);
+    let name = &f.name;
+    let method = ident(base);
+    let encode = ident(&format!("encode_{}", base));
+    let args: Vec<Ident> = f
+        .inputs
+        .iter()
+        .map(|p| match p.name.as_str() {
+            // not to clash with the value of the transaction
+            "tx_value" if f.payable => ident("tx_value_"),
+            name => ident(name),
+        })
+        .collect();
+    let arg_types = f.inputs.iter().map(|p| &p.rust);
+    let params = quote!(#(#args: #arg_types),*);
+    let inputs: Vec<&str> = f.inputs.iter().map(|p| p.canonical.as_str()).collect();
+    let outputs: Vec<&str> = f.outputs.iter().map(|p| p.canonical.as_str()).collect();
+    let signature = format!("{}({})", name, inputs.join(","));
+
+    let encode_doc = format!("Encodes the calldata of `{}`.", signature);
+    let encode_fn = quote! {
+        #[doc = #encode_doc]
+        #[allow(clippy::too_many_arguments)]
+        pub fn #encode(#params) -> #anyhow::Result<::std::vec::Vec<u8>> {
+            #abi::encode_call(
+                #name,
+                &[#(#inputs),*],
+                ::std::vec![#(#abi::Tokenizable::into_token(#args)),*],
+            )
+        }
+    };
+
+    let call_fn = if f.view {
+        let (ret, decode) = match f.outputs.len() {
+            0 => (quote!(()), quote!(::core::result::Result::Ok(()))),
+            1 => {
+                let ty = &f.outputs[0].rust;
+                (
+                    quote!(#ty),
+                    quote!(<#ty as #abi::Tokenizable>::from_token(tokens.remove(0))),
+                )
+            }
+            _ => {
+                let tys = f.outputs.iter().map(|p| &p.rust);
+                let ty = quote!((#(#tys,)*));
+                (
+                    ty.clone(),
+                    quote!(<#ty as #abi::Tokenizable>::from_token(#abi::Token::Tuple(tokens))),
+                )
+            }
+        };
+        let doc = format!(
+            "Calls `{}` and decodes the returned `({})`.",
+            signature,
+            outputs.join(",")
+        );
+        quote! {
+            #[doc = #doc]
+            #[allow(clippy::too_many_arguments, unused_mut)]
+            pub fn #method(&self, #params) -> #anyhow::Result<#ret> {
+                let ret = #abi::call(self.chain_id, self.address, &Self::#encode(#(#args),*)?)?.into_data()?;
+                let mut tokens = #abi::decode_output(&[#(#outputs),*], &ret)?;
+                #decode
+            }
+        }
+    } else {
+        let (value_param, value) = if f.payable {
+            (quote!(tx_value: #abi::U256,), quote!(tx_value))
+        } else {
+            (quote!(), quote!(#abi::U256::ZERO))
+        };
+        let doc = if f.payable {
+            format!(
+                "Sends a transaction calling `{}` with `tx_value` wei, and returns the transaction hash.",
+                signature
+            )
+        } else {
+            format!(
+                "Sends a transaction calling `{}`, and returns the transaction hash.",
+                signature
+            )
+        };
+        quote! {
+            #[doc = #doc]
+            #[allow(clippy::too_many_arguments)]
+            pub fn #method(&self, #value_param #params) -> #anyhow::Result<#abi::H256> {
+                #abi::transact(self.chain_id, self.address, #value, &Self::#encode(#(#args),*)?)
+            }
+        }
+    };
+
+    quote!(#encode_fn #call_fn)
+}
+
+fn ident(name: &str) -> Ident {
+    match name {
+        // can't be raw identifiers
+        "self" | "Self" | "super" | "crate" => Ident::new(&format!("{}_", name), Span::call_site()),
+        _ => syn::parse_str::<Ident>(name)
+            .unwrap_or_else(|_| Ident::new_raw(name, Span::call_site())),
+    }
+}
+
+// `balanceOf` -> `balance_of`, `tokenURI` -> `token_uri`
+fn to_snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len() + 4);
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = matches!(chars.get(i + 1), Some(n) if n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
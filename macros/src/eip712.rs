@@ -0,0 +1,101 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result};
+
+struct Member {
+    ident: syn::Ident,
+    ty: syn::Type,
+    name: String,
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let mut name = input.ident.to_string();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("eip712")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported eip712 attribute, expected `name`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "expected named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "expected a struct")),
+    };
+
+    let mut members = Vec::with_capacity(fields.len());
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let mut member = Member {
+            name: ident.to_string().trim_start_matches("r#").to_string(),
+            ident,
+            ty: field.ty.clone(),
+        };
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("eip712")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    member.name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported eip712 attribute, expected `rename`"))
+                }
+            })?;
+        }
+        members.push(member);
+    }
+
+    let eip712 = quote!(::ws_sdk::crypto::eip712);
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let types = members.iter().map(|m| &m.ty).collect::<Vec<_>>();
+    let member_names = members.iter().map(|m| format!(" {}", m.name));
+    let idents = members.iter().map(|m| &m.ident);
+
+    Ok(quote! {
+        impl #impl_generics #eip712::Eip712Type for #ident #ty_generics #where_clause {
+            fn type_name() -> ::std::string::String {
+                ::std::string::String::from(#name)
+            }
+
+            fn collect_structs(
+                structs: &mut ::std::collections::BTreeMap<
+                    ::std::string::String,
+                    ::std::string::String,
+                >,
+            ) {
+                if structs.contains_key(#name) {
+                    return;
+                }
+                let members: ::std::vec::Vec<::std::string::String> = ::std::vec![
+                    #(<#types as #eip712::Eip712Type>::type_name() + #member_names),*
+                ];
+                structs.insert(
+                    ::std::string::String::from(#name),
+                    ::std::format!("{}({})", #name, members.join(",")),
+                );
+                #(<#types as #eip712::Eip712Type>::collect_structs(structs);)*
+            }
+
+            fn encode_value(&self) -> [u8; 32] {
+                *<Self as #eip712::Eip712>::struct_hash(self).as_bytes()
+            }
+        }
+
+        impl #impl_generics #eip712::Eip712Item for #ident #ty_generics #where_clause {}
+
+        impl #impl_generics #eip712::Eip712 for #ident #ty_generics #where_clause {
+            fn encode_data(&self) -> ::std::vec::Vec<u8> {
+                #[allow(unused_mut)]
+                let mut data = ::std::vec::Vec::new();
+                #(data.extend_from_slice(&#eip712::Eip712Type::encode_value(&self.#idents));)*
+                data
+            }
+        }
+    })
+}
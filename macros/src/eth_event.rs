@@ -0,0 +1,127 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result};
+
+struct Param {
+    ident: syn::Ident,
+    ty: syn::Type,
+    indexed: bool,
+    abi: Option<String>,
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let mut name = input.ident.to_string();
+    let mut anonymous = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("ethevent")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("anonymous") {
+                anonymous = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported ethevent attribute, expected `name` or `anonymous`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "expected named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "expected a struct")),
+    };
+
+    let mut params = Vec::with_capacity(fields.len());
+    for field in fields {
+        let mut param = Param {
+            ident: field.ident.clone().unwrap(),
+            ty: field.ty.clone(),
+            indexed: false,
+            abi: None,
+        };
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("ethevent")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("indexed") {
+                    param.indexed = true;
+                    Ok(())
+                } else if meta.path.is_ident("abi") {
+                    param.abi = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported ethevent attribute, expected `indexed` or `abi`"))
+                }
+            })?;
+        }
+        params.push(param);
+    }
+    let indexed = params.iter().filter(|p| p.indexed).count();
+    if indexed > if anonymous { 4 } else { 3 } {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "too many indexed parameters of the event",
+        ));
+    }
+
+    let abi = quote!(::ws_sdk::blockchain::abi);
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let event_params = params.iter().map(|p| {
+        let name = p.ident.to_string().trim_start_matches("r#").to_string();
+        let ty = &p.ty;
+        let indexed = p.indexed;
+        let canonical = match &p.abi {
+            Some(abi) => quote!(::std::string::String::from(#abi)),
+            None => quote!(<#ty as #abi::AbiType>::param_type().to_string()),
+        };
+        quote! {
+            ::ws_sdk::blockchain::events::EventParam {
+                name: #name,
+                kind: <#ty as #abi::AbiType>::param_type(),
+                canonical: #canonical,
+                indexed: #indexed,
+            }
+        }
+    });
+    let decoded = params.iter().map(|p| {
+        let ident = &p.ident;
+        let ty = &p.ty;
+        quote!(#ident: <#ty as #abi::Tokenizable>::from_token(tokens.next().unwrap())?)
+    });
+    let len = params.len();
+
+    Ok(quote! {
+        impl #impl_generics ::ws_sdk::blockchain::events::EthEvent for #ident #ty_generics #where_clause {
+            fn name() -> &'static str {
+                #name
+            }
+
+            fn params() -> ::std::vec::Vec<::ws_sdk::blockchain::events::EventParam> {
+                ::std::vec![#(#event_params),*]
+            }
+
+            fn anonymous() -> bool {
+                #anonymous
+            }
+
+            fn from_tokens(
+                tokens: ::std::vec::Vec<#abi::Token>,
+            ) -> ::ws_sdk::__private::anyhow::Result<Self> {
+                if tokens.len() != #len {
+                    ::ws_sdk::__private::anyhow::bail!(
+                        "expected {} parameters of the event, found {}",
+                        #len,
+                        tokens.len()
+                    );
+                }
+                #[allow(unused_mut)]
+                let mut tokens = tokens.into_iter();
+                ::core::result::Result::Ok(Self {
+                    #(#decoded),*
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,86 @@
+//! Procedural macros of [ws-sdk](https://docs.rs/ws-sdk).
+//!
+//! The macros are re-exported by `ws-sdk` behind its feature flags, and
+//! shouldn't be used from this crate directly.
+use proc_macro::TokenStream;
+
+mod abigen;
+mod eip712;
+mod eth_event;
+mod sql_row;
+
+/// Derives `ws_sdk::database::sql::SqlRow` for a struct with named fields.
+///
+/// See `ws_sdk::database::sql::SqlRow` for the attributes.
+#[proc_macro_derive(SqlRow, attributes(sql))]
+pub fn derive_sql_row(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    sql_row::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates the bindings of a contract from its JSON ABI.
+///
+/// `abigen!(Name, "path/to/abi.json")` generates a struct `Name` bound to a
+/// contract by `Name::new(chain_id, address)`. The path is relative to the
+/// crate root, and the JSON may be an ABI array, a compiled artifact holding
+/// the ABI in its `abi` field, or an inline ABI array in the string literal.
+///
+/// For each function `fooBar` of the ABI, the struct has:
+///
+///  * `encode_foo_bar(args...)`, which encodes the calldata.
+///  * `foo_bar(&self, args...)`, which calls the contract and decodes the
+///    returned values if the function is `view` or `pure`, and sends a
+///    transaction and returns its hash otherwise. A `payable` function takes
+///    the value in wei as the first argument `tx_value`, and an argument of
+///    the ABI named `tx_value` is renamed `tx_value_`.
+///
+/// The first of the overloaded functions keeps the bare name, and the others
+/// are suffixed with `_1`, `_2`, ... in the order of the ABI. A name taken by
+/// another generated method, e.g. `encode_x` of both `encodeX` and the helper
+/// of `x`, or `new`, gets a trailing `_`.
+/// See `ws_sdk::blockchain::abi::Tokenizable` for the Rust types of the
+/// arguments and the returned values.
+///
+/// # Examples
+///
+/// ```ignore
+/// use ws_sdk::blockchain::abi::{abigen, U256};
+///
+/// abigen!(SetGet, "abi/set_get.json");
+///
+/// let contract = SetGet::new(4690, "0x3908c0620ABC34a23A078097e2e878AFf60bbC28".parse()?);
+/// let current: U256 = contract.get()?;
+/// let hash = contract.set(current + 1)?;
+/// ```
+#[proc_macro]
+pub fn abigen(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as abigen::Input);
+    abigen::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `ws_sdk::blockchain::events::EthEvent` for a struct with named
+/// fields.
+///
+/// See `ws_sdk::blockchain::events::EthEvent` for the attributes.
+#[proc_macro_derive(EthEvent, attributes(ethevent))]
+pub fn derive_eth_event(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    eth_event::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `ws_sdk::crypto::eip712::Eip712` for a struct with named fields.
+///
+/// See `ws_sdk::crypto::eip712::Eip712` for the attributes.
+#[proc_macro_derive(Eip712, attributes(eip712))]
+pub fn derive_eip712(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    eip712::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
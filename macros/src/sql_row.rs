@@ -0,0 +1,102 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result};
+
+struct Column {
+    ident: syn::Ident,
+    name: String,
+    skip: bool,
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let mut table = to_snake_case(&input.ident.to_string());
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("sql")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                table = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported sql attribute, expected `table`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "expected named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "expected a struct")),
+    };
+
+    let mut columns = Vec::with_capacity(fields.len());
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let mut column = Column {
+            name: ident.to_string().trim_start_matches("r#").to_string(),
+            ident,
+            skip: false,
+        };
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("sql")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    column.name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    column.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported sql attribute, expected `rename` or `skip`"))
+                }
+            })?;
+        }
+        columns.push(column);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let stored: Vec<&Column> = columns.iter().filter(|c| !c.skip).collect();
+    let column_names = stored.iter().map(|c| &c.name);
+    let value_idents = stored.iter().map(|c| &c.ident);
+    let decoded = columns.iter().map(|c| {
+        let ident = &c.ident;
+        let name = &c.name;
+        if c.skip {
+            quote!(#ident: ::core::default::Default::default())
+        } else {
+            quote!(#ident: row.get_as(#name)?)
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::ws_sdk::database::sql::SqlRow for #name #ty_generics #where_clause {
+            const TABLE: &'static str = #table;
+            const COLUMNS: &'static [&'static str] = &[#(#column_names),*];
+
+            fn sql_values(&self) -> ::std::vec::Vec<&dyn ::ws_sdk::database::sql::SQLType> {
+                ::std::vec![#(&self.#value_idents),*]
+            }
+
+            fn from_row(row: &::ws_sdk::database::sql::Row) -> ::ws_sdk::__private::anyhow::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    #(#decoded),*
+                })
+            }
+        }
+    })
+}
+
+fn to_snake_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 4);
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
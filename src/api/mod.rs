@@ -2,3 +2,4 @@
 //!
 mod api;
 pub use self::api::*;
+pub mod system;
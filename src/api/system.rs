@@ -0,0 +1,562 @@
+//! Typed wrappers of the system APIs of W3bstream:
+//!
+//! | API                       | Request                | Function            |
+//! |---------------------------|------------------------|---------------------|
+//! | `/system/read_tx`         | [`ReadTxRequest`]      | [`read_tx`]         |
+//! | `/system/read_tx_receipt` | [`TxReceiptRequest`]   | [`read_tx_receipt`] |
+//! | `/system/block_number`    | [`BlockNumberRequest`] | [`block_number`]    |
+//! | `/system/get_logs`        | [`LogFilter`]          | [`get_logs`]        |
+//! | `/system/balance`         | [`BalanceRequest`]     | [`balance`]         |
+//!
+//! Each API is a request struct implementing [`SystemRequest`], with the
+//! JSON body of the API as its fields and the response type of the API. A
+//! request is either sent synchronously with [`SystemRequest::send`], or
+//! routed with [`SystemRequest::send_routed`], whose result is delivered to
+//! the wasm later as an event of the given `EventType`, and is parsed with
+//! [`SystemRequest::parse_result`] in the handler of the event.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ws_sdk::api::system::{read_tx, ReadTxRequest, SystemRequest};
+//! use ws_sdk::blockchain::Chain;
+//! use ws_sdk::stream::get_data;
+//!
+//! let hash = "0xfcaf377ff3cc785d60c58de7e121d6a2e79e1c58c189ea8641f3ea61f7605285".parse()?;
+//!
+//! // wait for the result
+//! let tx = read_tx(&Chain::IotexTestnet, hash)?;
+//!
+//! // or route the result to the handler of the event type `result`
+//! ReadTxRequest::new(&Chain::IotexTestnet, hash).send_routed("result")?;
+//! // ... in the handler of `result`
+//! # let rid = 0;
+//! let tx = ReadTxRequest::parse_result(&get_data(rid)?)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+use super::api_call;
+use crate::blockchain::types::serde_helpers::{hex_bytes, opt_quantity, quantity};
+use crate::blockchain::types::{Address, Log, H256, U256};
+use crate::blockchain::Chain;
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use http::Request;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A request of a system API.
+pub trait SystemRequest: Serialize {
+    /// The path of the API, e.g. `/system/read_tx`.
+    const PATH: &'static str;
+    /// The HTTP method of the API.
+    const METHOD: &'static str = "GET";
+    /// The response of the API.
+    type Response: DeserializeOwned;
+
+    /// Builds the HTTP request of the API, with the `EventType` header to
+    /// route the result if `event_type` is given.
+    fn http_request(&self, event_type: Option<&str>) -> Result<Request<Vec<u8>>> {
+        let mut builder = Request::builder().method(Self::METHOD).uri(Self::PATH);
+        if let Some(event_type) = event_type {
+            builder = builder.header("EventType", event_type);
+        }
+        Ok(builder.body(serde_json::to_vec(self)?)?)
+    }
+
+    /// Calls the API and waits for its response.
+    fn send(&self) -> Result<Self::Response> {
+        let ret = api_call(self.http_request(None)?)?;
+        Self::parse_result(ret.as_bytes())
+    }
+
+    /// Calls the API without waiting, and the response is delivered as the
+    /// data of an event of `event_type`.
+    fn send_routed(&self, event_type: &str) -> Result<()> {
+        api_call(self.http_request(Some(event_type))?)?;
+        Ok(())
+    }
+
+    /// Parses the response of the API, either returned by [`send`] or
+    /// delivered as the data of the routed event.
+    ///
+    /// [`send`]: SystemRequest::send
+    fn parse_result(data: &[u8]) -> Result<Self::Response> {
+        let body = response_body(data)?;
+        serde_json::from_slice(&body)
+            .with_context(|| format!("invalid response of `{}`", Self::PATH))
+    }
+}
+
+#[derive(Deserialize)]
+struct HttpResponse {
+    #[serde(rename = "StatusCode")]
+    status_code: u32,
+    #[serde(rename = "Body", default)]
+    body: String,
+}
+
+// The response is the HTTP response of the host, whose body is in base64,
+// or the body itself.
+fn response_body(data: &[u8]) -> Result<Vec<u8>> {
+    let resp: HttpResponse = match serde_json::from_slice(data) {
+        Ok(resp) => resp,
+        Err(_) => return Ok(data.to_vec()),
+    };
+    let body = general_purpose::STANDARD
+        .decode(&resp.body)
+        .context("invalid body of the response")?;
+    if !(200..300).contains(&resp.status_code) {
+        bail!(
+            "the api responds with {}: {}",
+            resp.status_code,
+            String::from_utf8_lossy(&body)
+        );
+    }
+    Ok(body)
+}
+
+/// Reads a transaction by its hash, `/system/read_tx`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTxRequest {
+    /// The chain id.
+    #[serde(rename = "chainID")]
+    pub chain_id: u32,
+    /// The name of the chain, e.g. `iotex-testnet`.
+    pub chain_name: String,
+    /// The hash of the transaction.
+    pub hash: H256,
+}
+
+impl ReadTxRequest {
+    /// Creates the request of the transaction `hash` on the chain.
+    pub fn new(chain: &Chain, hash: H256) -> Self {
+        ReadTxRequest {
+            chain_id: chain.id(),
+            chain_name: chain.name().to_owned(),
+            hash,
+        }
+    }
+}
+
+impl SystemRequest for ReadTxRequest {
+    const PATH: &'static str = "/system/read_tx";
+    type Response = Option<Transaction>;
+}
+
+/// A transaction, which is `None` if it's not found.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    /// The hash of the transaction.
+    pub hash: H256,
+    /// The nonce of the sender.
+    #[serde(with = "quantity")]
+    pub nonce: u64,
+    /// The sender.
+    pub from: Address,
+    /// The recipient, `None` for a contract creation.
+    #[serde(default)]
+    pub to: Option<Address>,
+    /// The value transferred, in wei.
+    pub value: U256,
+    /// The gas limit.
+    #[serde(with = "quantity")]
+    pub gas: u64,
+    /// The gas price of a legacy transaction, in wei.
+    #[serde(default)]
+    pub gas_price: Option<U256>,
+    /// The max fee per gas of an EIP-1559 transaction, in wei.
+    #[serde(default)]
+    pub max_fee_per_gas: Option<U256>,
+    /// The max priority fee per gas of an EIP-1559 transaction, in wei.
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// The call data, or the init code of a contract creation.
+    #[serde(with = "hex_bytes")]
+    pub input: Vec<u8>,
+    /// The hash of the block, `None` for a pending transaction.
+    #[serde(default)]
+    pub block_hash: Option<H256>,
+    /// The number of the block, `None` for a pending transaction.
+    #[serde(default, with = "opt_quantity")]
+    pub block_number: Option<u64>,
+    /// The index in the block, `None` for a pending transaction.
+    #[serde(default, with = "opt_quantity")]
+    pub transaction_index: Option<u64>,
+}
+
+/// Reads the receipt of a transaction, `/system/read_tx_receipt`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxReceiptRequest {
+    /// The chain id.
+    #[serde(rename = "chainID")]
+    pub chain_id: u32,
+    /// The name of the chain, e.g. `iotex-testnet`.
+    pub chain_name: String,
+    /// The hash of the transaction.
+    pub hash: H256,
+}
+
+impl TxReceiptRequest {
+    /// Creates the request of the receipt of the transaction `hash` on the
+    /// chain.
+    pub fn new(chain: &Chain, hash: H256) -> Self {
+        TxReceiptRequest {
+            chain_id: chain.id(),
+            chain_name: chain.name().to_owned(),
+            hash,
+        }
+    }
+}
+
+impl SystemRequest for TxReceiptRequest {
+    const PATH: &'static str = "/system/read_tx_receipt";
+    type Response = Option<TxReceipt>;
+}
+
+/// The receipt of a mined transaction, which is `None` if the transaction is
+/// pending or not found.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxReceipt {
+    /// The hash of the transaction.
+    pub transaction_hash: H256,
+    /// The hash of the block of the transaction.
+    pub block_hash: H256,
+    /// The number of the block of the transaction.
+    #[serde(with = "quantity")]
+    pub block_number: u64,
+    /// The index of the transaction in its block.
+    #[serde(with = "quantity")]
+    pub transaction_index: u64,
+    /// The sender.
+    pub from: Address,
+    /// The recipient, `None` for a contract creation.
+    #[serde(default)]
+    pub to: Option<Address>,
+    /// The address of the created contract.
+    #[serde(default)]
+    pub contract_address: Option<Address>,
+    /// The gas used by the transaction.
+    #[serde(with = "quantity")]
+    pub gas_used: u64,
+    /// The gas used by the transaction and the ones before it in the block.
+    #[serde(with = "quantity")]
+    pub cumulative_gas_used: u64,
+    /// The price of the gas paid, in wei.
+    #[serde(default)]
+    pub effective_gas_price: Option<U256>,
+    /// 1 for success and 0 for failure.
+    #[serde(default, with = "opt_quantity")]
+    pub status: Option<u64>,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+impl TxReceipt {
+    /// Returns true if the transaction succeeded.
+    pub fn is_success(&self) -> bool {
+        self.status == Some(1)
+    }
+}
+
+/// Reads the latest block number, `/system/block_number`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockNumberRequest {
+    /// The chain id.
+    #[serde(rename = "chainID")]
+    pub chain_id: u32,
+    /// The name of the chain, e.g. `iotex-testnet`.
+    pub chain_name: String,
+}
+
+impl BlockNumberRequest {
+    /// Creates the request of the latest block number of the chain.
+    pub fn new(chain: &Chain) -> Self {
+        BlockNumberRequest {
+            chain_id: chain.id(),
+            chain_name: chain.name().to_owned(),
+        }
+    }
+}
+
+impl SystemRequest for BlockNumberRequest {
+    const PATH: &'static str = "/system/block_number";
+    type Response = BlockNumber;
+}
+
+/// The response of [`BlockNumberRequest`].
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockNumber {
+    /// The number of the latest block.
+    #[serde(with = "quantity")]
+    pub block_number: u64,
+}
+
+/// Reads the logs matching the filter, `/system/get_logs`.
+///
+/// The `topics` are matched by position, where an empty position matches
+/// any topic and several topics in a position match any of them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::api::system::{LogFilter, SystemRequest};
+/// use ws_sdk::blockchain::{types::keccak256, Chain};
+///
+/// let transfer = keccak256(b"Transfer(address,address,uint256)").into();
+/// let logs = LogFilter::new(&Chain::IotexTestnet)
+///     .address("0x3908c0620ABC34a23A078097e2e878AFf60bbC28".parse()?)
+///     .topic(0, &[transfer])
+///     .from_block(100)
+///     .send()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[skip_serializing_none]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFilter {
+    /// The chain id.
+    #[serde(rename = "chainID")]
+    pub chain_id: u32,
+    /// The name of the chain, e.g. `iotex-testnet`.
+    pub chain_name: String,
+    /// The contracts emitting the logs, any contract if `None`.
+    pub address: Option<Vec<Address>>,
+    /// The topics matched by position.
+    pub topics: Vec<Vec<H256>>,
+    /// The first block to search, the latest block if `None`.
+    pub from_block: Option<u64>,
+    /// The last block to search, the latest block if `None`.
+    pub to_block: Option<u64>,
+}
+
+impl LogFilter {
+    /// Creates a filter matching all the logs of the latest block of the
+    /// chain.
+    pub fn new(chain: &Chain) -> Self {
+        LogFilter {
+            chain_id: chain.id(),
+            chain_name: chain.name().to_owned(),
+            address: None,
+            topics: Vec::new(),
+            from_block: None,
+            to_block: None,
+        }
+    }
+
+    /// Adds a contract address emitting the logs.
+    pub fn address(mut self, address: Address) -> Self {
+        self.address.get_or_insert_with(Vec::new).push(address);
+        self
+    }
+
+    /// Matches the topic at `position` with any of `topics`.
+    pub fn topic(mut self, position: usize, topics: &[H256]) -> Self {
+        if self.topics.len() <= position {
+            self.topics.resize(position + 1, Vec::new());
+        }
+        self.topics[position] = topics.to_vec();
+        self
+    }
+
+    /// Sets the first block to search.
+    pub fn from_block(mut self, number: u64) -> Self {
+        self.from_block = Some(number);
+        self
+    }
+
+    /// Sets the last block to search.
+    pub fn to_block(mut self, number: u64) -> Self {
+        self.to_block = Some(number);
+        self
+    }
+}
+
+impl SystemRequest for LogFilter {
+    const PATH: &'static str = "/system/get_logs";
+    type Response = Vec<Log>;
+}
+
+/// Reads the balance of the native token of an account, `/system/balance`.
+#[skip_serializing_none]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceRequest {
+    /// The chain id.
+    #[serde(rename = "chainID")]
+    pub chain_id: u32,
+    /// The name of the chain, e.g. `iotex-testnet`.
+    pub chain_name: String,
+    /// The account.
+    pub address: Address,
+    /// The block number to read at, the latest block if `None`.
+    pub block_number: Option<u64>,
+}
+
+impl BalanceRequest {
+    /// Creates the request of the balance of `address` on the chain.
+    pub fn new(chain: &Chain, address: Address) -> Self {
+        BalanceRequest {
+            chain_id: chain.id(),
+            chain_name: chain.name().to_owned(),
+            address,
+            block_number: None,
+        }
+    }
+
+    /// Reads the balance at the block `number`.
+    pub fn at_block(mut self, number: u64) -> Self {
+        self.block_number = Some(number);
+        self
+    }
+}
+
+impl SystemRequest for BalanceRequest {
+    const PATH: &'static str = "/system/balance";
+    type Response = Balance;
+}
+
+/// The response of [`BalanceRequest`].
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Balance {
+    /// The balance in the smallest unit of the native token.
+    pub balance: U256,
+}
+
+/// Reads a transaction by its hash.
+pub fn read_tx(chain: &Chain, hash: H256) -> Result<Option<Transaction>> {
+    ReadTxRequest::new(chain, hash).send()
+}
+
+/// Reads the receipt of a transaction by its hash.
+pub fn read_tx_receipt(chain: &Chain, hash: H256) -> Result<Option<TxReceipt>> {
+    TxReceiptRequest::new(chain, hash).send()
+}
+
+/// Reads the latest block number.
+pub fn block_number(chain: &Chain) -> Result<u64> {
+    Ok(BlockNumberRequest::new(chain).send()?.block_number)
+}
+
+/// Reads the logs matching the filter.
+pub fn get_logs(filter: &LogFilter) -> Result<Vec<Log>> {
+    filter.send()
+}
+
+/// Reads the balance of the native token of an account.
+pub fn balance(chain: &Chain, address: Address) -> Result<U256> {
+    Ok(BalanceRequest::new(chain, address).send()?.balance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_request() {
+        let hash = H256::from([0xab; 32]);
+        let req = ReadTxRequest::new(&Chain::IotexTestnet, hash)
+            .http_request(Some("result"))
+            .unwrap();
+        assert_eq!(req.uri(), "/system/read_tx");
+        assert_eq!(req.headers()["EventType"], "result");
+        assert_eq!(
+            String::from_utf8(req.into_body()).unwrap(),
+            format!(
+                r#"{{"chainID":4690,"chainName":"iotex-testnet","hash":"{}"}}"#,
+                hash
+            )
+        );
+
+        let filter = LogFilter::new(&Chain::IotexMainnet).topic(1, &[hash]);
+        assert_eq!(
+            serde_json::to_string(&filter).unwrap(),
+            format!(
+                r#"{{"chainID":4689,"chainName":"iotex-mainnet","topics":[[],["{}"]]}}"#,
+                hash
+            )
+        );
+        let req = BalanceRequest::new(&Chain::IotexMainnet, Address::from([0x11; 20]));
+        assert_eq!(
+            serde_json::to_string(&req.at_block(7)).unwrap(),
+            format!(
+                r#"{{"chainID":4689,"chainName":"iotex-mainnet","address":"{}","blockNumber":7}}"#,
+                Address::from([0x11; 20])
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_result() {
+        let body = r#"{
+            "hash": "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+            "nonce": "0x1",
+            "from": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "to": null,
+            "value": "0",
+            "gas": "0x5208",
+            "gasPrice": "1000000000000",
+            "input": "0x01",
+            "blockHash": null,
+            "blockNumber": null
+        }"#;
+        let resp = format!(
+            r#"{{"Status":"200 OK","StatusCode":200,"Body":"{}"}}"#,
+            general_purpose::STANDARD.encode(body)
+        );
+        let tx = ReadTxRequest::parse_result(resp.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.gas, 21000);
+        assert_eq!(tx.to, None);
+        assert_eq!(tx.input, vec![1]);
+        assert_eq!(tx.block_number, None);
+        assert_eq!(
+            ReadTxRequest::parse_result(body.as_bytes()).unwrap(),
+            Some(tx)
+        );
+        assert_eq!(ReadTxRequest::parse_result(b"null").unwrap(), None);
+
+        let resp = format!(
+            r#"{{"StatusCode":404,"Body":"{}"}}"#,
+            general_purpose::STANDARD.encode("not found")
+        );
+        let err = ReadTxRequest::parse_result(resp.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "the api responds with 404: not found");
+
+        let ret = BlockNumberRequest::parse_result(br#"{"blockNumber":"0x1b4"}"#).unwrap();
+        assert_eq!(ret.block_number, 436);
+
+        let receipt = r#"{
+            "transactionHash": "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "blockNumber": "0x10",
+            "transactionIndex": "0x0",
+            "from": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "to": null,
+            "contractAddress": "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359",
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "status": "0x1",
+            "logs": [{
+                "address": "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359",
+                "topics": [],
+                "data": "0x01",
+                "logIndex": "0x0"
+            }]
+        }"#;
+        let receipt = TxReceiptRequest::parse_result(receipt.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert!(receipt.is_success());
+        assert_eq!(receipt.gas_used, 21000);
+        assert_eq!(receipt.logs[0].data, vec![1]);
+        assert_eq!(TxReceiptRequest::parse_result(b"null").unwrap(), None);
+    }
+}
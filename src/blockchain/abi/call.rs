@@ -0,0 +1,149 @@
+use super::{AbiType, Address, ParamType, Token, H256, U256};
+use crate::blockchain::{call_contract, CallResult, TxRequest};
+use anyhow::{bail, Context, Result};
+
+/// Parses the canonical Solidity types, e.g. `uint256` or `(address,bytes)[]`.
+pub fn param_types(types: &[&str]) -> Result<Vec<ParamType>> {
+    types
+        .iter()
+        .map(|t| {
+            ethabi::param_type::Reader::read(t).with_context(|| format!("invalid abi type `{}`", t))
+        })
+        .collect()
+}
+
+// Converts between the signed and unsigned integer tokens as the type expects,
+// since both of them are encoded as 256-bit words.
+fn conform(token: Token, t: &ParamType) -> Token {
+    match (token, t) {
+        (Token::Uint(v), ParamType::Int(_)) => Token::Int(v),
+        (Token::Int(v), ParamType::Uint(_)) => Token::Uint(v),
+        (Token::Array(v), ParamType::Array(t)) => {
+            Token::Array(v.into_iter().map(|v| conform(v, t)).collect())
+        }
+        (Token::FixedArray(v), ParamType::FixedArray(t, _)) => {
+            Token::FixedArray(v.into_iter().map(|v| conform(v, t)).collect())
+        }
+        (Token::Tuple(v), ParamType::Tuple(ts)) if v.len() == ts.len() => {
+            Token::Tuple(v.into_iter().zip(ts).map(|(v, t)| conform(v, t)).collect())
+        }
+        (token, _) => token,
+    }
+}
+
+/// Encodes the calldata of the function `name` with the canonical types of
+/// its inputs, i.e. the selector followed by the encoded arguments.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::abi::{encode_call, Tokenizable, U256};
+/// let data = encode_call("set", &["uint256"], vec![U256::from(42u64).into_token()])?;
+/// assert_eq!(&data[..4], &[0x60, 0xfe, 0x47, 0xb1]);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn encode_call(name: &str, inputs: &[&str], args: Vec<Token>) -> Result<Vec<u8>> {
+    let types = param_types(inputs)?;
+    if types.len() != args.len() {
+        bail!(
+            "`{}` takes {} arguments, but {} are given",
+            name,
+            types.len(),
+            args.len()
+        );
+    }
+    let args: Vec<Token> = args
+        .into_iter()
+        .zip(&types)
+        .map(|(a, t)| conform(a, t))
+        .collect();
+    for (t, arg) in types.iter().zip(&args) {
+        if !arg.type_check(t) {
+            bail!("the argument {:?} of `{}` doesn't match `{}`", arg, name, t);
+        }
+    }
+    let mut data = ethabi::short_signature(name, &types).to_vec();
+    data.extend(ethabi::encode(&args));
+    Ok(data)
+}
+
+/// Decodes the returned data of a call with the canonical types of the outputs.
+pub fn decode_output(outputs: &[&str], data: &[u8]) -> Result<Vec<Token>> {
+    let types = param_types(outputs)?;
+    ethabi::decode(&types, data).context("fail to decode the returned data")
+}
+
+// The types of the values of `T`, i.e. the elements of a tuple, or `T` itself.
+pub(crate) fn abi_types<T: AbiType>() -> Vec<ParamType> {
+    match T::param_type() {
+        ParamType::Tuple(types) => types,
+        t => vec![t],
+    }
+}
+
+/// Decodes the ABI encoded values, e.g. the returned data of a call, as `T`.
+///
+/// A tuple is decoded as the list of the values, like the outputs of a
+/// function; a single tuple value, e.g. a returned struct, is decoded as a
+/// 1-tuple of it.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::abi::{decode, U256};
+/// let mut data = [0; 64];
+/// data[31] = 7;
+/// data[63] = 1;
+/// let (n, ok): (U256, bool) = decode(&data)?;
+/// assert_eq!((n, ok), (U256::from(7u64), true));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn decode<T: AbiType>(data: &[u8]) -> Result<T> {
+    let mut tokens =
+        ethabi::decode(&abi_types::<T>(), data).context("fail to decode the returned data")?;
+    match T::param_type() {
+        ParamType::Tuple(_) => T::from_token(Token::Tuple(tokens)),
+        _ => T::from_token(tokens.remove(0)),
+    }
+}
+
+/// Calls a contract with the calldata.
+///
+/// The calldata is hex encoded for [`call_contract`].
+pub fn call(chain_id: u32, to: Address, data: &[u8]) -> Result<CallResult> {
+    call_contract(chain_id, to, &hex::encode(data))
+}
+
+/// Sends a transaction with the calldata to a contract and returns the hash.
+///
+/// The calldata is hex encoded for [`send_tx`](crate::blockchain::send_tx), and `value` is in wei.
+/// See [`TxRequest::calldata`] for setting the other fields of the transaction.
+pub fn transact(chain_id: u32, to: Address, value: U256, data: &[u8]) -> Result<H256> {
+    TxRequest::new(to)
+        .value(value)
+        .calldata(data)
+        .send(chain_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let data = ethabi::encode(&[Token::Uint(7.into()), Token::Bool(true)]);
+        let ret = decode_output(&["uint256", "bool"], &data).unwrap();
+        assert_eq!(ret, vec![Token::Uint(7.into()), Token::Bool(true)]);
+        assert_eq!(decode::<(u8, bool)>(&data).unwrap(), (7, true));
+        assert_eq!(decode::<U256>(&data[..32]).unwrap(), U256::from(7u64));
+
+        let data = ethabi::encode(&[Token::Tuple(vec![
+            Token::String("a".into()),
+            Token::Uint(1.into()),
+        ])]);
+        assert_eq!(
+            decode::<((String, u64),)>(&data).unwrap(),
+            ((String::from("a"), 1),)
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Encode and decode the contract calls with the [Ethereum ABI].
+//!
+//! The values are converted into and from the ABI [`Token`]s through
+//! [`Tokenizable`]. Usually the calls are not encoded by hand, but through
+//! the bindings generated by [`abigen!`] from the JSON ABI of the contract.
+//!
+//! It's enabled by the `abi` feature flag.
+//!
+//! [Ethereum ABI]: https://docs.soliditylang.org/en/latest/abi-spec.html
+pub use super::types::{Address, H256, U256};
+pub use ethabi::{ParamType, Token};
+mod call;
+pub use self::call::*;
+mod token;
+pub use self::token::*;
+pub use ws_sdk_macros::abigen;
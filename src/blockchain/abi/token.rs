@@ -0,0 +1,408 @@
+use super::{Address, ParamType, Token, H256, U256};
+use anyhow::{bail, Result};
+
+/// A type which can be converted into and from an ABI [`Token`].
+///
+/// It's implemented for the Rust types of the Solidity types:
+///
+/// | Solidity                | Rust                              |
+/// |-------------------------|-----------------------------------|
+/// | `address`               | [`Address`]                       |
+/// | `uint8` ... `uint128`   | `u8` ... `u128`                   |
+/// | `int8` ... `int128`     | `i8` ... `i128`                   |
+/// | `uint256`, `int256`     | [`U256`], two's complement for int |
+/// | `bool`                  | `bool`                            |
+/// | `string`                | `String`                          |
+/// | `bytes`                 | `Vec<u8>`                         |
+/// | `bytes1` ... `bytes32`  | `[u8; 1]` ... `[u8; 32]`, or [`H256`] for `bytes32` |
+/// | `T[]`                   | `Vec<T>`                          |
+/// | `T[k]`                  | `[T; k]`                          |
+/// | `(T1, T2, ...)`         | `(T1, T2, ...)`                   |
+///
+/// A longer array of bytes has no ABI type, e.g. a 65-byte signature is a
+/// `Vec<u8>` of `bytes`:
+///
+/// ```compile_fail
+/// use ws_sdk::blockchain::abi::Tokenizable;
+/// let token = [0u8; 65].into_token();
+/// ```
+pub trait Tokenizable: Sized {
+    /// Converts the value into a token.
+    fn into_token(self) -> Token;
+    /// Converts a token into the value.
+    fn from_token(token: Token) -> Result<Self>;
+}
+
+/// A [`Tokenizable`] type which can be an element of an array.
+///
+/// It's implemented for all the tokenizable types except `u8`, whose arrays
+/// are encoded as bytes.
+pub trait TokenizableItem: Tokenizable {}
+
+/// A [`Tokenizable`] type with a fixed ABI type, so that it can be decoded
+/// without the types being given, e.g. by [`decode`](super::decode).
+///
+/// It's implemented for the types in the table of [`Tokenizable`], where the
+/// integers map to the ABI types of their sizes, e.g. `u32` to `uint32`.
+pub trait AbiType: Tokenizable {
+    /// Returns the ABI type.
+    fn param_type() -> ParamType;
+}
+
+fn mismatch<T>(expected: &str, token: &Token) -> Result<T> {
+    bail!("expected {}, found {:?}", expected, token)
+}
+
+impl Tokenizable for Token {
+    fn into_token(self) -> Token {
+        self
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        Ok(token)
+    }
+}
+
+// ethabi has its own primitive types, converted through the bytes
+fn to_uint(v: U256) -> ethabi::Uint {
+    ethabi::Uint::from_big_endian(&v.to_big_endian())
+}
+
+fn from_uint(v: ethabi::Uint) -> U256 {
+    let mut bytes = [0; 32];
+    v.to_big_endian(&mut bytes);
+    U256::from_big_endian(&bytes).expect("32 bytes fit in U256")
+}
+
+impl Tokenizable for Address {
+    fn into_token(self) -> Token {
+        Token::Address(ethabi::Address::from(<[u8; 20]>::from(self)))
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Address(v) => Ok(Address::from(v.0)),
+            t => mismatch("address", &t),
+        }
+    }
+}
+
+impl Tokenizable for U256 {
+    fn into_token(self) -> Token {
+        Token::Uint(to_uint(self))
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Uint(v) | Token::Int(v) => Ok(from_uint(v)),
+            t => mismatch("integer", &t),
+        }
+    }
+}
+
+impl Tokenizable for H256 {
+    fn into_token(self) -> Token {
+        Token::FixedBytes(self.as_bytes().to_vec())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::FixedBytes(v) if v.len() == 32 => H256::from_slice(&v),
+            t => mismatch("bytes32", &t),
+        }
+    }
+}
+
+impl Tokenizable for bool {
+    fn into_token(self) -> Token {
+        Token::Bool(self)
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Bool(v) => Ok(v),
+            t => mismatch("bool", &t),
+        }
+    }
+}
+
+impl Tokenizable for String {
+    fn into_token(self) -> Token {
+        Token::String(self)
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::String(v) => Ok(v),
+            t => mismatch("string", &t),
+        }
+    }
+}
+
+impl Tokenizable for Vec<u8> {
+    fn into_token(self) -> Token {
+        Token::Bytes(self)
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Bytes(v) | Token::FixedBytes(v) => Ok(v),
+            t => mismatch("bytes", &t),
+        }
+    }
+}
+
+// `bytes1` to `bytes32`, so that no other array of bytes has an ABI type.
+macro_rules! impl_tokenizable_for_bytes {
+    ($($n:literal),*) => {
+        $(
+            impl Tokenizable for [u8; $n] {
+                fn into_token(self) -> Token {
+                    Token::FixedBytes(self.to_vec())
+                }
+
+                fn from_token(token: Token) -> Result<Self> {
+                    match token {
+                        Token::FixedBytes(v) if v.len() == $n => {
+                            let mut out = [0; $n];
+                            out.copy_from_slice(&v);
+                            Ok(out)
+                        }
+                        t => mismatch(concat!("bytes", $n), &t),
+                    }
+                }
+            }
+
+            impl TokenizableItem for [u8; $n] {}
+
+            impl AbiType for [u8; $n] {
+                fn param_type() -> ParamType {
+                    ParamType::FixedBytes($n)
+                }
+            }
+        )*
+    };
+}
+
+impl_tokenizable_for_bytes!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32
+);
+
+impl<T: TokenizableItem> Tokenizable for Vec<T> {
+    fn into_token(self) -> Token {
+        Token::Array(self.into_iter().map(T::into_token).collect())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::Array(v) | Token::FixedArray(v) => v.into_iter().map(T::from_token).collect(),
+            t => mismatch("array", &t),
+        }
+    }
+}
+
+impl<T: TokenizableItem, const N: usize> Tokenizable for [T; N] {
+    fn into_token(self) -> Token {
+        Token::FixedArray(self.into_iter().map(T::into_token).collect())
+    }
+
+    fn from_token(token: Token) -> Result<Self> {
+        match token {
+            Token::FixedArray(v) if v.len() == N => {
+                let items = v
+                    .into_iter()
+                    .map(T::from_token)
+                    .collect::<Result<Vec<_>>>()?;
+                match items.try_into() {
+                    Ok(items) => Ok(items),
+                    Err(_) => unreachable!(),
+                }
+            }
+            t => mismatch(&format!("array of {} elements", N), &t),
+        }
+    }
+}
+
+macro_rules! impl_tokenizable_for_uint {
+    ($($t:ty),*) => {
+        $(
+            impl Tokenizable for $t {
+                fn into_token(self) -> Token {
+                    U256::from(self).into_token()
+                }
+
+                fn from_token(token: Token) -> Result<Self> {
+                    let v = U256::from_token(token)?;
+                    if v > U256::from(<$t>::MAX) {
+                        bail!("{} overflows {}", v, stringify!($t));
+                    }
+                    Ok(v.low_u128() as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_tokenizable_for_uint!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_tokenizable_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Tokenizable for $t {
+                fn into_token(self) -> Token {
+                    // two's complement in 256 bits
+                    if self < 0 {
+                        Token::Int(to_uint(!U256::from(!(self as i128) as u128)))
+                    } else {
+                        Token::Int(to_uint(U256::from(self as u128)))
+                    }
+                }
+
+                fn from_token(token: Token) -> Result<Self> {
+                    let v = U256::from_token(token)?;
+                    let (abs, negative) = if v.bit(255) { (!v, true) } else { (v, false) };
+                    if abs > U256::from(<$t>::MAX as u128) {
+                        bail!("{} overflows {}", v, stringify!($t));
+                    }
+                    let abs = abs.low_u128() as $t;
+                    Ok(if negative { !abs } else { abs })
+                }
+            }
+        )*
+    };
+}
+
+impl_tokenizable_for_int!(i8, i16, i32, i64, i128);
+
+macro_rules! impl_tokenizable_item {
+    ($($t:ty),*) => {
+        $(impl TokenizableItem for $t {})*
+    };
+}
+
+impl_tokenizable_item!(
+    Token,
+    Address,
+    U256,
+    H256,
+    bool,
+    String,
+    Vec<u8>,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128
+);
+
+impl<T: TokenizableItem> TokenizableItem for Vec<T> {}
+impl<T: TokenizableItem, const N: usize> TokenizableItem for [T; N] {}
+
+macro_rules! impl_abi_type {
+    ($($t:ty => $p:expr),*) => {
+        $(
+            impl AbiType for $t {
+                fn param_type() -> ParamType {
+                    $p
+                }
+            }
+        )*
+    };
+}
+
+impl_abi_type!(
+    Address => ParamType::Address,
+    U256 => ParamType::Uint(256),
+    H256 => ParamType::FixedBytes(32),
+    bool => ParamType::Bool,
+    String => ParamType::String,
+    Vec<u8> => ParamType::Bytes,
+    u8 => ParamType::Uint(8),
+    u16 => ParamType::Uint(16),
+    u32 => ParamType::Uint(32),
+    u64 => ParamType::Uint(64),
+    u128 => ParamType::Uint(128),
+    i8 => ParamType::Int(8),
+    i16 => ParamType::Int(16),
+    i32 => ParamType::Int(32),
+    i64 => ParamType::Int(64),
+    i128 => ParamType::Int(128)
+);
+
+impl<T: AbiType + TokenizableItem> AbiType for Vec<T> {
+    fn param_type() -> ParamType {
+        ParamType::Array(Box::new(T::param_type()))
+    }
+}
+
+impl<T: AbiType + TokenizableItem, const N: usize> AbiType for [T; N] {
+    fn param_type() -> ParamType {
+        ParamType::FixedArray(Box::new(T::param_type()), N)
+    }
+}
+
+macro_rules! impl_tokenizable_for_tuple {
+    ($($n:tt: $t:ident),+) => {
+        impl<$($t: Tokenizable),+> Tokenizable for ($($t,)+) {
+            fn into_token(self) -> Token {
+                Token::Tuple(vec![$(self.$n.into_token()),+])
+            }
+
+            fn from_token(token: Token) -> Result<Self> {
+                let len = [$($n),+].len();
+                match token {
+                    Token::Tuple(v) if v.len() == len => {
+                        let mut it = v.into_iter();
+                        Ok(($($t::from_token(it.next().unwrap())?,)+))
+                    }
+                    t => mismatch(&format!("tuple of {} elements", len), &t),
+                }
+            }
+        }
+
+        impl<$($t: Tokenizable),+> TokenizableItem for ($($t,)+) {}
+
+        impl<$($t: AbiType),+> AbiType for ($($t,)+) {
+            fn param_type() -> ParamType {
+                ParamType::Tuple(vec![$($t::param_type()),+])
+            }
+        }
+    };
+}
+
+impl_tokenizable_for_tuple!(0: A);
+impl_tokenizable_for_tuple!(0: A, 1: B);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C, 3: D);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C, 3: D, 4: E);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I);
+impl_tokenizable_for_tuple!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int_token() {
+        assert_eq!(Token::Int(ethabi::Uint::MAX), (-1_i8).into_token());
+        assert_eq!(i64::from_token(Token::Int(ethabi::Uint::MAX)).unwrap(), -1);
+        assert_eq!(i16::from_token((-300_i16).into_token()).unwrap(), -300);
+        assert_eq!(i8::from_token(i8::MIN.into_token()).unwrap(), i8::MIN);
+        assert!(i8::from_token((-129_i16).into_token()).is_err());
+        assert!(u8::from_token(256_u16.into_token()).is_err());
+
+        let v = (vec![1_u32, 2], [7_u8; 4], String::from("a"));
+        assert_eq!(
+            <(Vec<u32>, [u8; 4], String)>::from_token(v.clone().into_token()).unwrap(),
+            v
+        );
+    }
+}
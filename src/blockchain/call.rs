@@ -0,0 +1,256 @@
+use super::types::U256;
+use anyhow::{anyhow, Result};
+use std::fmt;
+
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// The result of [`call_contract`](super::call_contract): the returned data
+/// of a successful call, or the revert of a failed one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::blockchain::{call_contract, CallResult};
+/// let to = "0x83c9fb5da807e4427b59b6c90b545496394abf98".parse()?;
+/// match call_contract(4689, to, "6d4ce63c")? {
+///     CallResult::Success(data) => println!("returned 0x{}", hex::encode(data)),
+///     CallResult::Reverted(revert) => println!("{}", revert),
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallResult {
+    /// The ABI encoded returned data.
+    Success(Vec<u8>),
+    /// The call reverted.
+    Reverted(Revert),
+}
+
+impl CallResult {
+    /// Returns true if the call succeeded.
+    pub fn is_success(&self) -> bool {
+        matches!(self, CallResult::Success(_))
+    }
+
+    /// Returns the revert if the call reverted.
+    pub fn revert(&self) -> Option<&Revert> {
+        match self {
+            CallResult::Success(_) => None,
+            CallResult::Reverted(revert) => Some(revert),
+        }
+    }
+
+    /// Returns the returned data, or the revert as an error, which can be
+    /// downcast to [`Revert`].
+    pub fn into_data(self) -> Result<Vec<u8>> {
+        match self {
+            CallResult::Success(data) => Ok(data),
+            CallResult::Reverted(revert) => Err(anyhow!(revert)),
+        }
+    }
+
+    /// Decodes the returned data as `T`, or returns the revert as an error.
+    ///
+    /// See [`abi::decode`](super::abi::decode) for how the data is decoded.
+    ///
+    /// It's enabled by the `abi` feature flag.
+    #[cfg(feature = "abi")]
+    pub fn decode<T: super::abi::AbiType>(&self) -> Result<T> {
+        match self {
+            CallResult::Success(data) => super::abi::decode(data),
+            CallResult::Reverted(revert) => Err(anyhow!(revert.clone())),
+        }
+    }
+}
+
+/// The reason of a reverted call, decoded from the revert data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Revert {
+    /// `Error(string)`, by `revert("...")` or `require(cond, "...")`.
+    Error(String),
+    /// `Panic(uint256)` with the panic code, e.g. `0x11` for an arithmetic
+    /// overflow.
+    Panic(U256),
+    /// A custom error, with its selector and the ABI encoded arguments.
+    Custom {
+        /// The first 4 bytes of the `keccak256` hash of the error signature.
+        selector: [u8; 4],
+        /// The ABI encoded arguments after the selector.
+        data: Vec<u8>,
+    },
+    /// The revert data without a selector, e.g. empty by `revert()`.
+    Unknown(Vec<u8>),
+}
+
+impl Revert {
+    /// Decodes the revert data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ws_sdk::blockchain::Revert;
+    /// let data = hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011")?;
+    /// assert_eq!(
+    ///     Revert::from_data(data).to_string(),
+    ///     "execution reverted: panic code 0x11 (arithmetic overflow or underflow)"
+    /// );
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_data(data: Vec<u8>) -> Self {
+        if data.len() < 4 {
+            return Revert::Unknown(data);
+        }
+        let mut selector = [0; 4];
+        selector.copy_from_slice(&data[..4]);
+        let args = &data[4..];
+        let decoded = match selector {
+            ERROR_SELECTOR => decode_string(args).map(Revert::Error),
+            PANIC_SELECTOR if args.len() == 32 => {
+                U256::from_big_endian(args).ok().map(Revert::Panic)
+            }
+            _ => None,
+        };
+        decoded.unwrap_or_else(|| Revert::Custom {
+            selector,
+            data: args.to_vec(),
+        })
+    }
+
+    /// Returns the reason of `Error(string)`.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Revert::Error(reason) => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Decodes the custom error `name` with the arguments of `T`, e.g.
+    /// `(Address, U256)` for `InsufficientBalance(address,uint256)`.
+    ///
+    /// Returns `None` if the revert is not the error, which is told by the
+    /// selector from the name and the ABI types of `T`.
+    ///
+    /// It's enabled by the `abi` feature flag.
+    #[cfg(feature = "abi")]
+    pub fn custom<T: super::abi::AbiType>(&self, name: &str) -> Option<T> {
+        match self {
+            Revert::Custom { selector, data } => {
+                let types = super::abi::abi_types::<T>();
+                if ethabi::short_signature(name, &types) != *selector {
+                    return None;
+                }
+                super::abi::decode(data).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+// The meanings of the panic codes of Solidity.
+fn panic_description(code: u64) -> Option<&'static str> {
+    Some(match code {
+        0x00 => "generic compiler panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array",
+        0x31 => "pop on an empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "too much memory allocated",
+        0x51 => "call to a zero-initialized function",
+        _ => return None,
+    })
+}
+
+impl fmt::Display for Revert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("execution reverted")?;
+        match self {
+            Revert::Error(reason) => write!(f, ": {}", reason),
+            Revert::Panic(code) => {
+                write!(f, ": panic code {:#x}", code)?;
+                match code.to_u64().and_then(panic_description) {
+                    Some(desc) => write!(f, " ({})", desc),
+                    None => Ok(()),
+                }
+            }
+            Revert::Custom { selector, data } => write!(
+                f,
+                ": custom error 0x{} with 0x{}",
+                hex::encode(selector),
+                hex::encode(data)
+            ),
+            Revert::Unknown(data) if data.is_empty() => Ok(()),
+            Revert::Unknown(data) => write!(f, ": 0x{}", hex::encode(data)),
+        }
+    }
+}
+
+impl std::error::Error for Revert {}
+
+// Decodes the ABI encoded `string`, which is the offset of its length, then
+// the length and the bytes.
+fn decode_string(args: &[u8]) -> Option<String> {
+    let word = |at: usize| -> Option<usize> {
+        let w = args.get(at..at.checked_add(32)?)?;
+        if w[..24].iter().any(|b| *b != 0) {
+            return None;
+        }
+        usize::try_from(u64::from_be_bytes(w[24..].try_into().ok()?)).ok()
+    };
+    let offset = word(0)?;
+    let len = word(offset)?;
+    let start = offset.checked_add(32)?;
+    let bytes = args.get(start..start.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(n: u8) -> [u8; 32] {
+        let mut w = [0; 32];
+        w[31] = n;
+        w
+    }
+
+    #[test]
+    fn test_revert() {
+        let mut data = ERROR_SELECTOR.to_vec();
+        data.extend(word(32));
+        data.extend(word(9));
+        data.extend(b"not owner");
+        data.extend([0; 23]);
+        let revert = Revert::from_data(data);
+        assert_eq!(revert.reason(), Some("not owner"));
+        assert_eq!(revert.to_string(), "execution reverted: not owner");
+
+        let mut data = PANIC_SELECTOR.to_vec();
+        data.extend(word(0x12));
+        assert_eq!(Revert::from_data(data), Revert::Panic(U256::from(0x12u64)));
+
+        let data = vec![0xde, 0xad, 0xbe, 0xef, 1];
+        assert_eq!(
+            Revert::from_data(data).to_string(),
+            "execution reverted: custom error 0xdeadbeef with 0x01"
+        );
+        assert_eq!(Revert::from_data(vec![]).to_string(), "execution reverted");
+
+        // a malformed string is kept as is
+        let mut data = ERROR_SELECTOR.to_vec();
+        data.extend(word(32));
+        data.extend(word(64));
+        assert!(matches!(Revert::from_data(data), Revert::Custom { .. }));
+
+        let err = CallResult::Reverted(Revert::Panic(U256::ONE))
+            .into_data()
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<Revert>(),
+            Some(&Revert::Panic(U256::ONE))
+        );
+    }
+}
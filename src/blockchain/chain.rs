@@ -0,0 +1,484 @@
+use super::types::{Address, H256, U256};
+use crate::stream::get_env;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// The key of the project environment variable holding the custom chains.
+///
+/// Its value is a JSON array of [`CustomChain`]s.
+pub const CUSTOM_CHAINS_ENV: &str = "WS_CUSTOM_CHAINS";
+
+/// A blockchain supported by W3bstream, or a chain defined by the project.
+///
+/// A chain is parsed from its name, e.g. `"iotex-testnet"`, which is also
+/// used by the system APIs of W3bstream, or from its chain id.
+///
+/// A built-in chain is serialized as its name, and a custom chain as its
+/// [`CustomChain`] object, so that both are deserialized back.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::Chain;
+/// let chain: Chain = "iotex-testnet".parse()?;
+/// assert_eq!(chain, Chain::IotexTestnet);
+/// assert_eq!(chain.id(), 4690);
+/// assert_eq!("4689".parse::<Chain>()?, Chain::IotexMainnet);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Chain {
+    /// `iotex-mainnet`, chain id 4689.
+    IotexMainnet,
+    /// `iotex-testnet`, chain id 4690.
+    IotexTestnet,
+    /// `ethereum-mainnet`, chain id 1.
+    Ethereum,
+    /// `ethereum-sepolia`, chain id 11155111.
+    Sepolia,
+    /// `polygon-mainnet`, chain id 137.
+    Polygon,
+    /// `polygon-amoy`, chain id 80002.
+    PolygonAmoy,
+    /// `bsc-mainnet`, chain id 56.
+    Bsc,
+    /// `bsc-testnet`, chain id 97.
+    BscTestnet,
+    /// `arbitrum-one`, chain id 42161.
+    Arbitrum,
+    /// `optimism-mainnet`, chain id 10.
+    Optimism,
+    /// `base-mainnet`, chain id 8453.
+    Base,
+    /// A chain defined by the project, see [`Chain::resolve`].
+    Custom(CustomChain),
+}
+
+/// A chain defined by the project in the environment variable
+/// [`CUSTOM_CHAINS_ENV`], e.g.
+///
+/// ```json
+/// [{ "name": "my-l2", "id": 12345, "symbol": "ETH", "explorer": "https://explorer.my-l2.io" }]
+/// ```
+///
+/// The `symbol` defaults to `ETH`, and the `decimals` to 18.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CustomChain {
+    /// The name of the chain, matched case insensitively.
+    pub name: String,
+    /// The chain id.
+    pub id: u32,
+    /// The symbol of the native token.
+    #[serde(default = "default_symbol")]
+    pub symbol: String,
+    /// The decimals of the native token, at most
+    /// [`U256::MAX_DECIMALS`].
+    #[serde(default = "default_decimals")]
+    pub decimals: u32,
+    /// The base URL of the explorer, like `https://etherscan.io`.
+    #[serde(default)]
+    pub explorer: Option<String>,
+}
+
+fn default_symbol() -> String {
+    "ETH".to_owned()
+}
+
+fn default_decimals() -> u32 {
+    18
+}
+
+impl CustomChain {
+    // Checks the chain doesn't conflict with a built-in one, and its amounts
+    // fit in a `U256`.
+    fn check(&self) -> Result<()> {
+        if let Ok(chain) = self.name.parse::<Chain>() {
+            bail!(
+                "the custom chain `{}` conflicts with `{}`",
+                self.name,
+                chain
+            );
+        }
+        if let Some(chain) = Chain::from_id(self.id) {
+            bail!(
+                "the chain id {} of the custom chain `{}` is taken by `{}`",
+                self.id,
+                self.name,
+                chain
+            );
+        }
+        if self.decimals > U256::MAX_DECIMALS {
+            bail!(
+                "the decimals {} of the custom chain `{}` are more than {}",
+                self.decimals,
+                self.name,
+                U256::MAX_DECIMALS
+            );
+        }
+        Ok(())
+    }
+}
+
+struct Spec {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    id: u32,
+    symbol: &'static str,
+    explorer: &'static str,
+}
+
+impl Chain {
+    /// The chains supported by W3bstream.
+    pub const BUILTIN: [Chain; 11] = [
+        Chain::IotexMainnet,
+        Chain::IotexTestnet,
+        Chain::Ethereum,
+        Chain::Sepolia,
+        Chain::Polygon,
+        Chain::PolygonAmoy,
+        Chain::Bsc,
+        Chain::BscTestnet,
+        Chain::Arbitrum,
+        Chain::Optimism,
+        Chain::Base,
+    ];
+
+    fn spec(&self) -> Option<Spec> {
+        let (name, aliases, id, symbol, explorer): (_, &'static [&'static str], _, _, _) =
+            match self {
+                Chain::IotexMainnet => (
+                    "iotex-mainnet",
+                    &["iotex"],
+                    4689,
+                    "IOTX",
+                    "https://iotexscan.io",
+                ),
+                Chain::IotexTestnet => (
+                    "iotex-testnet",
+                    &[],
+                    4690,
+                    "IOTX",
+                    "https://testnet.iotexscan.io",
+                ),
+                Chain::Ethereum => (
+                    "ethereum-mainnet",
+                    &["ethereum", "mainnet"],
+                    1,
+                    "ETH",
+                    "https://etherscan.io",
+                ),
+                Chain::Sepolia => (
+                    "ethereum-sepolia",
+                    &["sepolia"],
+                    11155111,
+                    "ETH",
+                    "https://sepolia.etherscan.io",
+                ),
+                Chain::Polygon => (
+                    "polygon-mainnet",
+                    &["polygon", "matic"],
+                    137,
+                    "POL",
+                    "https://polygonscan.com",
+                ),
+                Chain::PolygonAmoy => (
+                    "polygon-amoy",
+                    &["amoy"],
+                    80002,
+                    "POL",
+                    "https://amoy.polygonscan.com",
+                ),
+                Chain::Bsc => ("bsc-mainnet", &["bsc"], 56, "BNB", "https://bscscan.com"),
+                Chain::BscTestnet => ("bsc-testnet", &[], 97, "BNB", "https://testnet.bscscan.com"),
+                Chain::Arbitrum => (
+                    "arbitrum-one",
+                    &["arbitrum", "arbitrum-mainnet"],
+                    42161,
+                    "ETH",
+                    "https://arbiscan.io",
+                ),
+                Chain::Optimism => (
+                    "optimism-mainnet",
+                    &["optimism"],
+                    10,
+                    "ETH",
+                    "https://optimistic.etherscan.io",
+                ),
+                Chain::Base => (
+                    "base-mainnet",
+                    &["base"],
+                    8453,
+                    "ETH",
+                    "https://basescan.org",
+                ),
+                Chain::Custom(_) => return None,
+            };
+        Some(Spec {
+            name,
+            aliases,
+            id,
+            symbol,
+            explorer,
+        })
+    }
+
+    /// Returns the built-in chain with the chain id.
+    pub fn from_id(id: u32) -> Option<Chain> {
+        Chain::BUILTIN.into_iter().find(|c| c.id() == id)
+    }
+
+    /// Returns the name of the chain, e.g. `iotex-testnet`.
+    pub fn name(&self) -> &str {
+        match self {
+            Chain::Custom(c) => &c.name,
+            _ => self.spec().unwrap().name,
+        }
+    }
+
+    /// Returns the chain id.
+    pub fn id(&self) -> u32 {
+        match self {
+            Chain::Custom(c) => c.id,
+            _ => self.spec().unwrap().id,
+        }
+    }
+
+    /// Returns the symbol of the native token, e.g. `IOTX`.
+    pub fn symbol(&self) -> &str {
+        match self {
+            Chain::Custom(c) => &c.symbol,
+            _ => self.spec().unwrap().symbol,
+        }
+    }
+
+    /// Returns the decimals of the native token.
+    pub fn decimals(&self) -> u32 {
+        match self {
+            Chain::Custom(c) => c.decimals,
+            _ => 18,
+        }
+    }
+
+    /// Returns the base URL of the explorer.
+    pub fn explorer(&self) -> Option<&str> {
+        match self {
+            Chain::Custom(c) => c.explorer.as_deref(),
+            _ => Some(self.spec().unwrap().explorer),
+        }
+    }
+
+    /// Returns the explorer URL of a transaction, i.e. `{explorer}/tx/{hash}`.
+    pub fn tx_url(&self, hash: &H256) -> Option<String> {
+        self.explorer_url("tx", &hash.to_string())
+    }
+
+    /// Returns the explorer URL of an address, i.e. `{explorer}/address/{address}`.
+    pub fn address_url(&self, address: &Address) -> Option<String> {
+        self.explorer_url("address", &address.to_string())
+    }
+
+    /// Returns the explorer URL of a block, i.e. `{explorer}/block/{number}`.
+    pub fn block_url(&self, number: u64) -> Option<String> {
+        self.explorer_url("block", &number.to_string())
+    }
+
+    fn explorer_url(&self, kind: &str, id: &str) -> Option<String> {
+        self.explorer()
+            .map(|base| format!("{}/{}/{}", base.trim_end_matches('/'), kind, id))
+    }
+
+    /// Parses an amount of the native token into its smallest unit, e.g.
+    /// `"1.5"` IOTX into `1500000000000000000` Rau.
+    pub fn parse_amount(&self, amount: &str) -> Result<U256> {
+        U256::parse_units(amount, self.decimals())
+    }
+
+    /// Formats an amount in the smallest unit of the native token.
+    pub fn format_amount(&self, amount: &U256) -> String {
+        amount.format_units(self.decimals())
+    }
+
+    /// Parses the custom chains from the JSON array of [`CustomChain`]s.
+    ///
+    /// The name and the chain id of a custom chain should be different from
+    /// the ones of the built-in chains and the other custom chains, where the
+    /// names are compared case insensitively.
+    pub fn parse_custom(json: &str) -> Result<Vec<Chain>> {
+        let chains: Vec<CustomChain> =
+            serde_json::from_str(json).context("invalid custom chains")?;
+        let (mut ids, mut names) = (HashSet::new(), HashSet::new());
+        chains
+            .into_iter()
+            .map(|c| {
+                c.check()?;
+                if !ids.insert(c.id) {
+                    bail!("the chain id {} is defined more than once", c.id);
+                }
+                if !names.insert(c.name.to_ascii_lowercase()) {
+                    bail!("the custom chain `{}` is defined more than once", c.name);
+                }
+                Ok(Chain::Custom(c))
+            })
+            .collect()
+    }
+
+    /// Loads the custom chains defined by the project in the environment
+    /// variable [`CUSTOM_CHAINS_ENV`].
+    pub fn custom_chains() -> Result<Vec<Chain>> {
+        Chain::parse_custom(&get_env(CUSTOM_CHAINS_ENV)?)
+    }
+
+    /// Resolves a chain by its name or chain id, among the built-in chains and
+    /// then the custom chains of the project.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ws_sdk::blockchain::Chain;
+    /// let chain = Chain::resolve("my-l2")?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn resolve(name: &str) -> Result<Chain> {
+        if let Ok(chain) = name.parse() {
+            return Ok(chain);
+        }
+        let id = name.parse::<u32>().ok();
+        Chain::custom_chains()?
+            .into_iter()
+            .find(|c| c.name().eq_ignore_ascii_case(name) || Some(c.id()) == id)
+            .with_context(|| format!("unknown chain `{}`", name))
+    }
+}
+
+impl From<Chain> for u32 {
+    fn from(chain: Chain) -> Self {
+        chain.id()
+    }
+}
+
+impl FromStr for Chain {
+    type Err = anyhow::Error;
+
+    /// Parses a built-in chain by its name, alias or chain id. The names are
+    /// case insensitive, with `_` taken as `-`.
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(id) = s.parse::<u32>() {
+            return Chain::from_id(id).with_context(|| format!("unknown chain id {}", id));
+        }
+        let name = s.trim().to_ascii_lowercase().replace('_', "-");
+        Chain::BUILTIN
+            .into_iter()
+            .find(|c| {
+                let spec = c.spec().unwrap();
+                spec.name == name || spec.aliases.contains(&name.as_str())
+            })
+            .with_context(|| format!("unknown chain `{}`", s))
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Serialize for Chain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Chain::Custom(c) => c.serialize(serializer),
+            _ => serializer.serialize_str(self.name()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChainRepr {
+    Builtin(String),
+    Custom(CustomChain),
+}
+
+impl<'de> Deserialize<'de> for Chain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match ChainRepr::deserialize(deserializer)? {
+            ChainRepr::Builtin(s) => s.parse().map_err(serde::de::Error::custom),
+            ChainRepr::Custom(c) => {
+                c.check().map_err(serde::de::Error::custom)?;
+                Ok(Chain::Custom(c))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain() {
+        for chain in Chain::BUILTIN {
+            assert_eq!(chain.name().parse::<Chain>().unwrap(), chain);
+            assert_eq!(Chain::from_id(chain.id()), Some(chain));
+        }
+        assert_eq!(
+            "IOTEX_TESTNET".parse::<Chain>().unwrap(),
+            Chain::IotexTestnet
+        );
+        assert_eq!("sepolia".parse::<Chain>().unwrap(), Chain::Sepolia);
+        assert!("iotex-devnet".parse::<Chain>().is_err());
+        assert!("12345".parse::<Chain>().is_err());
+
+        assert_eq!(
+            Chain::IotexMainnet.tx_url(&H256::ZERO).unwrap(),
+            format!("https://iotexscan.io/tx/{}", H256::ZERO)
+        );
+        assert_eq!(
+            Chain::IotexTestnet.parse_amount("1.5").unwrap(),
+            U256::from(15u64) * U256::exp10(17).unwrap()
+        );
+
+        let chains = Chain::parse_custom(
+            r#"[{"name":"my-l2","id":12345,"decimals":6,"explorer":"https://scan.my-l2.io/"}]"#,
+        )
+        .unwrap();
+        assert_eq!(chains[0].id(), 12345);
+        assert_eq!(chains[0].symbol(), "ETH");
+        assert_eq!(chains[0].format_amount(&U256::from(1_500_000u64)), "1.5");
+        assert_eq!(
+            chains[0].block_url(7).unwrap(),
+            "https://scan.my-l2.io/block/7"
+        );
+        assert!(Chain::parse_custom(r#"[{"name":"iotex","id":1}]"#).is_err());
+        assert!(Chain::parse_custom(r#"[{"name":"my-l2","id":4690}]"#).is_err());
+        assert!(Chain::parse_custom(
+            r#"[{"name":"my-l2","id":12345},{"name":"my-l3","id":12345}]"#
+        )
+        .is_err());
+        assert!(Chain::parse_custom(
+            r#"[{"name":"my-l2","id":12345},{"name":"my-l2","id":12346}]"#
+        )
+        .is_err());
+        assert!(Chain::parse_custom(
+            r#"[{"name":"my-l2","id":12345},{"name":"MY-L2","id":12346}]"#
+        )
+        .is_err());
+        assert!(Chain::parse_custom(r#"[{"name":"my-l2","id":12345,"decimals":78}]"#).is_err());
+        assert!(
+            Chain::parse_custom(r#"[{"name":"my-l2","id":12345,"decimals":4000000000}]"#).is_err()
+        );
+        assert_eq!(
+            serde_json::to_string(&Chain::IotexTestnet).unwrap(),
+            r#""iotex-testnet""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Chain>(r#""iotex-testnet""#).unwrap(),
+            Chain::IotexTestnet
+        );
+        let json = serde_json::to_string(&chains[0]).unwrap();
+        assert_eq!(serde_json::from_str::<Chain>(&json).unwrap(), chains[0]);
+        assert!(serde_json::from_str::<Chain>(r#"{"name":"my-l2","id":4690}"#).is_err());
+    }
+}
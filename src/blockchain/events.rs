@@ -0,0 +1,247 @@
+//! Decode the logs of the contract events which trigger the handlers.
+//!
+//! The log of an event is delivered as the data of the handler, which is
+//! parsed into a [`Log`] by [`get_log`]. A log is matched with an event by
+//! its first topic, the hash of the event signature, and the parameters of
+//! the event are decoded from the other topics, for the indexed ones, and
+//! the data, for the others.
+//!
+//! An event is declared by a struct deriving [`EthEvent`], whose fields are
+//! the parameters of the event:
+//!
+//! ```ignore
+//! use ws_sdk::blockchain::events::{get_log, EthEvent};
+//! use ws_sdk::blockchain::types::{Address, U256};
+//!
+//! #[derive(EthEvent)]
+//! struct Transfer {
+//!     #[ethevent(indexed)]
+//!     from: Address,
+//!     #[ethevent(indexed)]
+//!     to: Address,
+//!     value: U256,
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn handle_transfer(rid: i32) -> i32 {
+//!     let log = get_log(rid as _).unwrap();
+//!     if Transfer::matches(&log) {
+//!         let transfer = Transfer::decode_log(&log).unwrap();
+//!     }
+//!     0
+//! }
+//! ```
+//!
+//! It's enabled by the `abi` feature flag.
+use super::abi::{ParamType, Token};
+use super::types::{keccak256, Log, H256};
+use crate::stream::get_data;
+use anyhow::{bail, Context, Result};
+
+/// Derives [`EthEvent`] for a struct with named fields.
+///
+/// The name of the event is the name of the struct, unless it's given by
+/// `#[ethevent(name = "...")]`, and an anonymous event is marked by
+/// `#[ethevent(anonymous)]`. The fields are the parameters in order, where
+/// an indexed one is marked by `#[ethevent(indexed)]`.
+///
+/// An indexed parameter of a reference type, e.g. `string` or an array, is
+/// only stored as its hash in the topic, so it's declared as [`H256`] with
+/// its Solidity type for the signature, e.g.
+/// `#[ethevent(indexed, abi = "string")]`.
+pub use ws_sdk_macros::EthEvent;
+
+/// Parses the log delivered as the data of the resource `resource_id`.
+pub fn get_log(resource_id: u32) -> Result<Log> {
+    parse_log(&get_data(resource_id)?)
+}
+
+/// Parses a log in the JSON of the node.
+pub fn parse_log(data: &[u8]) -> Result<Log> {
+    serde_json::from_slice(data).context("invalid log")
+}
+
+/// Returns the topic of an event signature, e.g.
+/// `Transfer(address,address,uint256)`.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::events::event_topic;
+/// assert_eq!(
+///     event_topic("Transfer(address,address,uint256)").to_string(),
+///     "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+/// );
+/// ```
+pub fn event_topic(signature: &str) -> H256 {
+    H256::from(keccak256(signature.as_bytes()))
+}
+
+/// A parameter of an event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventParam {
+    /// The name of the parameter in the contract.
+    pub name: &'static str,
+    /// The type to decode the parameter.
+    pub kind: ParamType,
+    /// The Solidity type in the signature, which differs from `kind` for an
+    /// indexed parameter stored as its hash.
+    pub canonical: String,
+    /// Whether the parameter is stored in the topics instead of the data.
+    pub indexed: bool,
+}
+
+/// An event of a contract, usually derived by [`derive@EthEvent`].
+pub trait EthEvent: Sized {
+    /// Returns the name of the event.
+    fn name() -> &'static str;
+
+    /// Returns the parameters of the event in order.
+    fn params() -> Vec<EventParam>;
+
+    /// Returns true if the event is anonymous, i.e. without the topic of its
+    /// signature.
+    fn anonymous() -> bool {
+        false
+    }
+
+    /// Creates the event from the tokens of the parameters in order.
+    fn from_tokens(tokens: Vec<Token>) -> Result<Self>;
+
+    /// Returns the signature, e.g. `Transfer(address,address,uint256)`.
+    fn signature() -> String {
+        let types: Vec<String> = Self::params().into_iter().map(|p| p.canonical).collect();
+        format!("{}({})", Self::name(), types.join(","))
+    }
+
+    /// Returns the topic of the signature.
+    fn topic() -> H256 {
+        event_topic(&Self::signature())
+    }
+
+    /// Returns true if the log is of the event, by the topic of the signature
+    /// and the number of the indexed parameters.
+    fn matches(log: &Log) -> bool {
+        let indexed = Self::params().iter().filter(|p| p.indexed).count();
+        if Self::anonymous() {
+            log.topics.len() == indexed
+        } else {
+            log.topics.len() == indexed + 1 && log.topics[0] == Self::topic()
+        }
+    }
+
+    /// Decodes the event from the log.
+    fn decode_log(log: &Log) -> Result<Self> {
+        let topics = if Self::anonymous() {
+            &log.topics[..]
+        } else {
+            match log.topics.split_first() {
+                Some((topic, topics)) if *topic == Self::topic() => topics,
+                _ => bail!("the log is not the event `{}`", Self::signature()),
+            }
+        };
+        let tokens = decode_params(&Self::params(), topics, &log.data)
+            .with_context(|| format!("fail to decode the event `{}`", Self::signature()))?;
+        Self::from_tokens(tokens)
+    }
+}
+
+/// Decodes the parameters from the topics of the indexed ones, without the
+/// topic of the signature, and the data of the others.
+pub fn decode_params(params: &[EventParam], topics: &[H256], data: &[u8]) -> Result<Vec<Token>> {
+    let indexed = params.iter().filter(|p| p.indexed).count();
+    if topics.len() != indexed {
+        bail!(
+            "expected {} indexed parameters, found {} topics",
+            indexed,
+            topics.len()
+        );
+    }
+    let types: Vec<ParamType> = params
+        .iter()
+        .filter(|p| !p.indexed)
+        .map(|p| p.kind.clone())
+        .collect();
+    let mut values = ethabi::decode(&types, data)
+        .context("invalid data of the log")?
+        .into_iter();
+    let mut topics = topics.iter();
+    params
+        .iter()
+        .map(|p| {
+            if !p.indexed {
+                return Ok(values.next().unwrap());
+            }
+            let topic = topics.next().unwrap();
+            match p.kind {
+                ParamType::Address
+                | ParamType::Uint(_)
+                | ParamType::Int(_)
+                | ParamType::Bool
+                | ParamType::FixedBytes(_) => {}
+                _ => bail!(
+                    "the indexed parameter `{}` is stored as its hash, and decoded as `H256`",
+                    p.name
+                ),
+            }
+            Ok(ethabi::decode(std::slice::from_ref(&p.kind), topic.as_bytes())?.remove(0))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::abi::{AbiType, Tokenizable};
+    use crate::blockchain::types::{Address, U256};
+
+    fn param(name: &'static str, kind: ParamType, indexed: bool) -> EventParam {
+        EventParam {
+            name,
+            canonical: kind.to_string(),
+            kind,
+            indexed,
+        }
+    }
+
+    #[test]
+    fn test_decode_params() {
+        let params = vec![
+            param("from", Address::param_type(), true),
+            param("memo", String::param_type(), false),
+            param("value", U256::param_type(), true),
+        ];
+        let from = Address::from([0x11; 20]);
+        let mut topic = [0; 32];
+        topic[12..].copy_from_slice(from.as_bytes());
+        let value = H256::from(U256::from(7u64).to_big_endian());
+        let data = ethabi::encode(&[Token::String("hi".into())]);
+        let tokens = decode_params(&params, &[H256::from(topic), value], &data).unwrap();
+        assert_eq!(Address::from_token(tokens[0].clone()).unwrap(), from);
+        assert_eq!(tokens[1], Token::String("hi".into()));
+        assert_eq!(
+            U256::from_token(tokens[2].clone()).unwrap(),
+            U256::from(7u64)
+        );
+        assert!(decode_params(&params, &[value], &data).is_err());
+
+        let params = vec![param("memo", String::param_type(), true)];
+        assert!(decode_params(&params, &[value], &[]).is_err());
+
+        let log = parse_log(
+            br#"{
+                "address": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                "topics": ["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"],
+                "data": "0x",
+                "blockNumber": "0x10",
+                "transactionHash": "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            log.topics[0],
+            event_topic("Transfer(address,address,uint256)")
+        );
+        assert_eq!(log.block_number, Some(16));
+    }
+}
@@ -1,71 +1,86 @@
 use super::super::host::abi::*;
+use super::types::{Address, H256, U256};
+use super::{CallResult, Revert, TxRequest};
+#[cfg(feature = "host-raw-tx")]
+use anyhow::Context;
 use anyhow::{bail, Result};
 use serde::Serialize;
 
-#[derive(Serialize)]
-struct Tx {
-    to: String,
-    data: String,
-    value: String,
-}
-
 /// Send a transaction to the blockchain whose chain id is `chain_id`.
 ///
 /// The transaction is signed with the private key of the node operator.
-/// The hash of transaction is returned as a string.
+/// The `value` is in wei, and `data` is the hex encoded calldata.
+/// See [`TxRequest`] for setting the gas, fees and nonce.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use ws-sdk::blockchain::send_tx
-/// let hash = send_tx(4689, "0x83c9fb5da807e4427b59b6c90b545496394abf98", "0", "")?;
+/// use ws_sdk::blockchain::{send_tx, types::U256};
+/// let to = "0x83c9fb5da807e4427b59b6c90b545496394abf98".parse()?;
+/// let hash = send_tx(4689, to, U256::ZERO, "")?;
+/// # Ok::<(), anyhow::Error>(())
 /// ```
-pub fn send_tx(chain_id: u32, to: &str, value: &str, data: &str) -> Result<String> {
-    let tx = Tx {
-        to: to.to_owned(),
-        value: value.to_owned(),
-        data: data.to_owned(),
-    };
-    let str = serde_json::to_string(&tx)?;
-    let data_ptr = &mut (0 as i32) as *const _ as *const *mut u8;
-    let data_size = &mut (0 as i32) as *const i32;
+pub fn send_tx(chain_id: u32, to: Address, value: U256, data: &str) -> Result<H256> {
+    TxRequest::new(to).value(value).data(data).send(chain_id)
+}
+
+/// Send a signed transaction to the blockchain whose chain id is `chain_id`.
+///
+/// The transaction is encoded in `raw`, e.g. by
+/// [`Wallet::sign_tx`](super::signer::Wallet::sign_tx), and is sent as is.
+/// The hash of the transaction is returned.
+///
+/// It's enabled by the `host-raw-tx` feature flag.
+#[cfg(feature = "host-raw-tx")]
+pub fn send_raw_tx(chain_id: u32, raw: &[u8]) -> Result<H256> {
+    let data_ptr = &mut 0_i32 as *const _ as *const *mut u8;
+    let data_size = &mut 0_i32 as *const i32;
     match unsafe {
-        ws_send_tx(
+        ws_send_raw_tx(
             chain_id as _,
-            str.as_ptr(),
-            str.len() as _,
+            raw.as_ptr(),
+            raw.len() as _,
             data_ptr,
             data_size,
         )
     } {
-        0 => Ok(unsafe { String::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) }),
-        _ => bail!("fail to send tx"),
+        0 => unsafe { String::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) }
+            .parse()
+            .context("invalid tx hash returned by the host"),
+        _ => bail!("fail to send raw tx"),
     }
 }
 
 #[derive(Serialize)]
 struct Call {
-    to: String,
+    to: Address,
     data: String,
 }
 
 /// Call a contract on the blockchain whose chain id is `chain_id`.
 ///
+/// The calldata `data` is hex encoded. The host returns the raw bytes of the
+/// ABI encoded returned data with the code 0, or the raw bytes of the revert
+/// data with a non-zero code if the call reverted, and nothing otherwise on a
+/// failure. The returned bytes are never hex encoded.
+///
 /// # Examples
 ///
 /// ```no_run
-/// use ws-sdk::blockchain::call_contract
-/// let hex = call_contract(4689, "0x83c9fb5da807e4427b59b6c90b545496394abf98", "");
+/// use ws_sdk::blockchain::call_contract;
+/// let to = "0x83c9fb5da807e4427b59b6c90b545496394abf98".parse()?;
+/// let data = call_contract(4689, to, "6d4ce63c")?.into_data()?;
+/// # Ok::<(), anyhow::Error>(())
 /// ```
-pub fn call_contract(chain_id: u32, to: &str, data: &str) -> Result<Vec<u8>> {
+pub fn call_contract(chain_id: u32, to: Address, data: &str) -> Result<CallResult> {
     let tx = Call {
-        to: to.to_owned(),
+        to,
         data: data.to_owned(),
     };
     let str = serde_json::to_string(&tx)?;
-    let data_ptr = &mut (0 as i32) as *const _ as *const *mut u8;
-    let data_size = &mut (0 as i32) as *const i32;
-    match unsafe {
+    let data_ptr = &mut 0_i32 as *const _ as *const *mut u8;
+    let data_size = &mut 0_i32 as *const i32;
+    let code = unsafe {
         ws_call_contract(
             chain_id as _,
             str.as_ptr(),
@@ -73,8 +88,14 @@ pub fn call_contract(chain_id: u32, to: &str, data: &str) -> Result<Vec<u8>> {
             data_ptr,
             data_size,
         )
-    } {
-        0 => Ok(unsafe { Vec::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) }),
+    };
+    let ret = match unsafe { *data_size } {
+        0 => Vec::new(),
+        size => unsafe { Vec::from_raw_parts(*data_ptr, size as _, size as _) },
+    };
+    match code {
+        0 => Ok(CallResult::Success(ret)),
+        _ if !ret.is_empty() => Ok(CallResult::Reverted(Revert::from_data(ret))),
         _ => bail!("fail to call contract"),
     }
 }
@@ -9,3 +9,20 @@
 //! the result is returned from the blockchain.
 mod jrpc;
 pub use self::jrpc::*;
+mod tx;
+pub use self::tx::*;
+mod call;
+pub use self::call::*;
+mod chain;
+pub use self::chain::*;
+#[cfg(feature = "abi")]
+pub mod abi;
+#[cfg(feature = "abi")]
+pub mod events;
+#[cfg(feature = "abi")]
+pub mod multicall;
+#[cfg(feature = "crypto")]
+pub mod signer;
+#[cfg(feature = "abi")]
+pub mod tokens;
+pub mod types;
@@ -0,0 +1,215 @@
+//! Batch the contract calls into one call of [Multicall3].
+//!
+//! Every [`call_contract`](super::call_contract) is a round trip to the
+//! host, so the calls of a handler are collected by a [`Multicall`] and made
+//! at once by `aggregate3` of the Multicall3 contract, which is deployed at
+//! [`MULTICALL3_ADDRESS`] on most of the chains.
+//!
+//! ```no_run
+//! use ws_sdk::blockchain::abi::{encode_call, Tokenizable, U256};
+//! use ws_sdk::blockchain::multicall::Multicall;
+//! use ws_sdk::blockchain::types::Address;
+//!
+//! let token: Address = "0x3908c0620ABC34a23A078097e2e878AFf60bbC28".parse()?;
+//! let owners: Vec<Address> = vec![Address::from([0x11; 20]), Address::from([0x22; 20])];
+//! let mut multicall = Multicall::new(4690);
+//! for owner in &owners {
+//!     multicall.add(token, encode_call("balanceOf", &["address"], vec![owner.into_token()])?);
+//! }
+//! for result in multicall.call()? {
+//!     let balance: U256 = result.decode()?;
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! It's enabled by the `abi` feature flag.
+//!
+//! [Multicall3]: https://github.com/mds1/multicall
+use super::abi::{self, Tokenizable};
+use super::types::Address;
+use super::{CallResult, Revert};
+use anyhow::{bail, Context, Result};
+
+/// The address of Multicall3 deployed by the same deployer on most of the
+/// chains, including the IoTeX mainnet and testnet.
+pub const MULTICALL3_ADDRESS: Address = Address::new([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
+/// A call in the batch, i.e. `Call3` of Multicall3.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    /// The contract to call.
+    pub target: Address,
+    /// If false, the whole batch reverts when the call fails.
+    pub allow_failure: bool,
+    /// The ABI-encoded call data, i.e. the selector and the arguments.
+    pub data: Vec<u8>,
+}
+
+/// A builder collecting the contract calls, which are made by one
+/// `aggregate3` call of Multicall3.
+#[derive(Debug, Clone)]
+pub struct Multicall {
+    chain_id: u32,
+    address: Address,
+    calls: Vec<Call>,
+}
+
+impl Multicall {
+    /// Creates an empty batch on the chain, with Multicall3 at
+    /// [`MULTICALL3_ADDRESS`].
+    pub fn new(chain_id: u32) -> Self {
+        Multicall {
+            chain_id,
+            address: MULTICALL3_ADDRESS,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Sets the address of Multicall3, for a chain where it's deployed
+    /// elsewhere.
+    pub fn address(&mut self, address: Address) -> &mut Self {
+        self.address = address;
+        self
+    }
+
+    /// Adds a call of `target` with the calldata, whose failure is returned
+    /// as its result.
+    pub fn add(&mut self, target: Address, data: Vec<u8>) -> &mut Self {
+        self.calls.push(Call {
+            target,
+            allow_failure: true,
+            data,
+        });
+        self
+    }
+
+    /// Adds a call of `target` with the calldata, whose failure reverts the
+    /// whole batch.
+    pub fn add_required(&mut self, target: Address, data: Vec<u8>) -> &mut Self {
+        self.calls.push(Call {
+            target,
+            allow_failure: false,
+            data,
+        });
+        self
+    }
+
+    /// Returns the calls in order.
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Returns the number of the calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns true if no call is added.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Encodes the calldata of `aggregate3` with the calls.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let calls: Vec<(Address, bool, Vec<u8>)> = self
+            .calls
+            .iter()
+            .map(|c| (c.target, c.allow_failure, c.data.clone()))
+            .collect();
+        abi::encode_call(
+            "aggregate3",
+            &["(address,bool,bytes)[]"],
+            vec![calls.into_token()],
+        )
+    }
+
+    /// Makes the calls by one call of Multicall3, and returns their results
+    /// in order.
+    ///
+    /// An error is returned if the batch reverts, e.g. by a failed call
+    /// added by [`add_required`](Multicall::add_required), which can be
+    /// downcast to [`Revert`].
+    pub fn call(&self) -> Result<Vec<CallResult>> {
+        if self.calls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let data = abi::call(self.chain_id, self.address, &self.encode()?)?
+            .into_data()
+            .context("fail to call multicall")?;
+        let results = decode_results(&data)?;
+        if results.len() != self.calls.len() {
+            bail!(
+                "expected {} results of multicall, found {}",
+                self.calls.len(),
+                results.len()
+            );
+        }
+        Ok(results)
+    }
+}
+
+/// Decodes the returned data of `aggregate3`, i.e. `(bool success, bytes
+/// returnData)[]`, into the results of the calls.
+pub fn decode_results(data: &[u8]) -> Result<Vec<CallResult>> {
+    let results: Vec<(bool, Vec<u8>)> =
+        abi::decode(data).context("invalid returned data of multicall")?;
+    Ok(results
+        .into_iter()
+        .map(|(success, data)| match success {
+            true => CallResult::Success(data),
+            false => CallResult::Reverted(Revert::from_data(data)),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::abi::Token;
+    use crate::blockchain::types::U256;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            MULTICALL3_ADDRESS.to_string(),
+            "0xcA11bde05977b3631167028862bE2a173976CA11"
+        );
+        let mut multicall = Multicall::new(4690);
+        assert!(multicall.is_empty());
+        let target = Address::from([0x11; 20]);
+        multicall
+            .add(target, vec![0x6d, 0x4c, 0xe6, 0x3c])
+            .add_required(target, vec![]);
+        let data = multicall.encode().unwrap();
+        // aggregate3((address,bool,bytes)[])
+        assert_eq!(&data[..4], &[0x82, 0xad, 0x56, 0xcb]);
+        let calls: Vec<(Address, bool, Vec<u8>)> = abi::decode(&data[4..]).unwrap();
+        assert_eq!(
+            calls,
+            vec![
+                (target, true, vec![0x6d, 0x4c, 0xe6, 0x3c]),
+                (target, false, vec![])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_results() {
+        let revert =
+            abi::encode_call("Error", &["string"], vec![Token::String("no".into())]).unwrap();
+        let data = ethabi::encode(&[vec![
+            (true, U256::from(7u64).to_big_endian().to_vec()),
+            (false, revert),
+        ]
+        .into_token()]);
+        let results = decode_results(&data).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].decode::<U256>().unwrap(), U256::from(7u64));
+        assert_eq!(results[1].revert(), Some(&Revert::Error("no".into())));
+        assert!(results[1].decode::<U256>().is_err());
+        assert!(decode_results(&[0x01]).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+//! Sign the transactions in the wasm with a key of the project.
+//!
+//! The transactions sent by [`send_tx`](super::send_tx) are signed by the
+//! node operator. A project may instead sign the transactions with its own
+//! key, e.g. kept in the environment variables, by a [`Wallet`], which is
+//! then the sender and the payer of them. The nonce and the gas are set by
+//! the project as the node doesn't fill them.
+//!
+//! The [`Transaction`]s are encoded in RLP as the legacy, [EIP-2930] and
+//! [EIP-1559] transactions, and the signed ones are sent by
+//! [`send_raw_tx`](super::send_raw_tx) with the `host-raw-tx` feature.
+//!
+//! It's enabled by the `crypto` feature flag.
+//!
+//! [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+//! [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+mod rlp;
+mod transaction;
+pub use self::transaction::*;
+mod wallet;
+pub use self::wallet::*;
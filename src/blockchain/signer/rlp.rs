@@ -0,0 +1,88 @@
+//! The Recursive Length Prefix encoding of the transactions.
+//! See <https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp/>.
+use crate::blockchain::types::U256;
+
+fn encode_length(out: &mut Vec<u8>, len: usize, offset: u8) {
+    if len <= 55 {
+        out.push(offset + len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        out.push(offset + 55 + (bytes.len() - skip) as u8);
+        out.extend(&bytes[skip..]);
+    }
+}
+
+/// A list being encoded, whose items are appended in order.
+#[derive(Default)]
+pub(crate) struct RlpList {
+    payload: Vec<u8>,
+}
+
+impl RlpList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            self.payload.push(bytes[0]);
+        } else {
+            encode_length(&mut self.payload, bytes.len(), 0x80);
+            self.payload.extend(bytes);
+        }
+        self
+    }
+
+    // an integer is its big-endian bytes without the leading zeros
+    pub fn uint(&mut self, v: U256) -> &mut Self {
+        let bytes = v.to_big_endian();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        self.bytes(&bytes[skip..])
+    }
+
+    pub fn u64(&mut self, v: u64) -> &mut Self {
+        self.uint(U256::from(v))
+    }
+
+    pub fn list(&mut self, list: &RlpList) -> &mut Self {
+        self.payload.extend(list.out());
+        self
+    }
+
+    pub fn out(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.payload.len() + 9);
+        encode_length(&mut out, self.payload.len(), 0xc0);
+        out.extend(&self.payload);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rlp() {
+        assert_eq!(RlpList::new().out(), vec![0xc0]);
+        let out = RlpList::new()
+            .bytes(b"cat")
+            .bytes(b"dog")
+            .uint(U256::ZERO)
+            .u64(15)
+            .u64(1024)
+            .out();
+        assert_eq!(
+            out,
+            vec![
+                0xcd, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g', 0x80, 0x0f, 0x82, 0x04, 0x00
+            ]
+        );
+        let long = [b'a'; 56];
+        let out = RlpList::new().bytes(&long).out();
+        assert_eq!(&out[..3], &[0xf8, 58, 0xb8]);
+        assert_eq!(out[3], 56);
+        let nested = RlpList::new().list(&RlpList::new()).out();
+        assert_eq!(nested, vec![0xc1, 0xc0]);
+    }
+}
@@ -0,0 +1,160 @@
+use super::transaction::{SignedTransaction, Transaction, TxSignature};
+use crate::blockchain::types::{Address, H256, U256};
+use crate::crypto::secp256k1::{address_of, get_signingkey, sign_prehash};
+use crate::stream::get_env;
+use anyhow::Result;
+use k256::ecdsa::SigningKey;
+
+/// A private key signing the transactions in the wasm, which is the sender
+/// and the payer of them.
+///
+/// The signed transaction is sent by [`Wallet::send_tx`] with the
+/// `host-raw-tx` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::blockchain::signer::{Transaction, TxFields, Wallet};
+/// use ws_sdk::blockchain::{types::U256, Chain};
+///
+/// let wallet = Wallet::from_env("SIGNER_KEY")?;
+/// let to = "0x3908c0620ABC34a23A078097e2e878AFf60bbC28".parse()?;
+/// let tx = Transaction::Eip1559 {
+///     fields: TxFields::new(Chain::IotexTestnet.id(), 7, 100_000, to)
+///         .data(hex::decode("6d4ce63c")?),
+///     max_fee_per_gas: U256::from_gwei("1000")?,
+///     max_priority_fee_per_gas: U256::ZERO,
+///     access_list: vec![],
+/// };
+/// let signed = wallet.sign_tx(&tx)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct Wallet {
+    key: SigningKey,
+    address: Address,
+}
+
+impl Wallet {
+    /// Creates a wallet from the private key in hex.
+    pub fn from_hex(prikey_hex: &str) -> Result<Self> {
+        let key = get_signingkey(prikey_hex)?;
+        let address = address_of(key.verifying_key());
+        Ok(Wallet { key, address })
+    }
+
+    /// Creates a wallet from the private key in hex in the environment
+    /// variable `key` of the project.
+    pub fn from_env(key: &str) -> Result<Self> {
+        Wallet::from_hex(get_env(key)?.trim())
+    }
+
+    /// Returns the address of the wallet.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signs a 32-byte hash.
+    pub fn sign_hash(&self, hash: &H256) -> Result<TxSignature> {
+        let sig = sign_prehash(&self.key, hash)?;
+        Ok(TxSignature {
+            y_parity: sig.recovery_id()?,
+            r: U256::from_big_endian(sig.r.as_bytes())?,
+            s: U256::from_big_endian(sig.s.as_bytes())?,
+        })
+    }
+
+    /// Signs the transaction and encodes it.
+    pub fn sign_tx(&self, tx: &Transaction) -> Result<SignedTransaction> {
+        let sig = self.sign_hash(&tx.signing_hash())?;
+        Ok(tx.encode_signed(&sig))
+    }
+
+    /// Signs the transaction and sends it by
+    /// [`send_raw_tx`](crate::blockchain::send_raw_tx).
+    ///
+    /// It's enabled by the `host-raw-tx` feature flag.
+    #[cfg(feature = "host-raw-tx")]
+    pub fn send_tx(&self, tx: &Transaction) -> Result<H256> {
+        let signed = self.sign_tx(tx)?;
+        crate::blockchain::send_raw_tx(tx.fields().chain_id, &signed.raw)
+    }
+}
+
+impl std::fmt::Debug for Wallet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never print the private key
+        f.debug_struct("Wallet")
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TxFields;
+    use super::*;
+    use crate::blockchain::types::keccak256;
+
+    #[test]
+    fn test_sign_tx() {
+        // the example of EIP-155
+        let wallet =
+            Wallet::from_hex("4646464646464646464646464646464646464646464646464646464646464646")
+                .unwrap();
+        assert_eq!(
+            wallet.address().to_string(),
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        let to = Address::from([0x35; 20]);
+        let tx = Transaction::Legacy {
+            fields: TxFields::new(1, 9, 21000, to).value(U256::from_ether("1").unwrap()),
+            gas_price: U256::from_gwei("20").unwrap(),
+        };
+        assert_eq!(
+            tx.signing_hash().to_string(),
+            "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        assert_eq!(
+            hex::encode(wallet.sign_tx(&tx).unwrap().raw),
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        );
+    }
+
+    #[test]
+    fn test_sign_typed_tx() {
+        let wallet =
+            Wallet::from_hex("0x4646464646464646464646464646464646464646464646464646464646464646")
+                .unwrap();
+        let fields = TxFields::new(4690, 0, 21000, Address::from([0x35; 20]));
+        let access_list = vec![crate::blockchain::AccessListItem {
+            address: Address::ZERO,
+            storage_keys: vec![H256::ZERO],
+        }];
+        let tx = Transaction::Eip1559 {
+            fields,
+            max_fee_per_gas: U256::from_gwei("1000").unwrap(),
+            max_priority_fee_per_gas: U256::ZERO,
+            access_list,
+        };
+        let signed = wallet.sign_tx(&tx).unwrap();
+        assert_eq!(signed.raw[0], 0x02);
+        // a list after the type
+        assert_eq!(signed.raw[1], 0xf8);
+        assert_eq!(signed.hash, H256::from(keccak256(&signed.raw)));
+
+        let sig = wallet.sign_hash(&tx.signing_hash()).unwrap();
+        let (sig, recid) = (
+            k256::ecdsa::Signature::from_scalars(sig.r.to_big_endian(), sig.s.to_big_endian())
+                .unwrap(),
+            k256::ecdsa::RecoveryId::from_byte(sig.y_parity).unwrap(),
+        );
+        let key = k256::ecdsa::VerifyingKey::recover_from_prehash(
+            tx.signing_hash().as_bytes(),
+            &sig,
+            recid,
+        )
+        .unwrap();
+        assert_eq!(&key, wallet.key.verifying_key());
+    }
+}
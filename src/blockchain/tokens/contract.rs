@@ -0,0 +1,28 @@
+use crate::blockchain::abi::{self, AbiType};
+use crate::blockchain::types::{Address, H256, U256};
+use anyhow::Result;
+
+// A contract bound to its chain, shared by the token clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Contract {
+    pub chain_id: u32,
+    pub address: Address,
+}
+
+impl Contract {
+    // Calls the contract with the calldata and decodes the returned value.
+    pub fn view<T: AbiType>(&self, data: Vec<u8>) -> Result<T> {
+        abi::call(self.chain_id, self.address, &data)?.decode()
+    }
+
+    // Sends a transaction with the calldata to the contract.
+    pub fn send(&self, data: Vec<u8>) -> Result<H256> {
+        abi::transact(self.chain_id, self.address, U256::ZERO, &data)
+    }
+}
+
+// Returns the function selector of the calldata in hex.
+#[cfg(test)]
+pub(super) fn selector(data: Vec<u8>) -> String {
+    hex::encode(&data[..4])
+}
@@ -0,0 +1,248 @@
+use super::contract::Contract;
+use crate::blockchain::abi::{encode_call, Tokenizable};
+use crate::blockchain::types::{Address, H256, U256};
+use anyhow::{bail, Result};
+
+/// A client of an [ERC-1155] multi token.
+///
+/// Besides the standard functions, it mints the tokens by `mint` of the
+/// usual mintable contracts, e.g. by OpenZeppelin, which is only allowed to
+/// the minter.
+///
+/// [ERC-1155]: https://eips.ethereum.org/EIPS/eip-1155
+#[derive(Debug, Clone)]
+pub struct Erc1155 {
+    contract: Contract,
+}
+
+impl Erc1155 {
+    /// Creates a client of the token at `address` on the chain.
+    pub fn new(chain_id: u32, address: Address) -> Self {
+        Erc1155 {
+            contract: Contract { chain_id, address },
+        }
+    }
+
+    /// Returns the id of the chain of the token.
+    pub fn chain_id(&self) -> u32 {
+        self.contract.chain_id
+    }
+
+    /// Returns the address of the token contract.
+    pub fn address(&self) -> Address {
+        self.contract.address
+    }
+
+    /// Returns the balance of the token `id` of `account`.
+    pub fn balance_of(&self, account: Address, id: U256) -> Result<U256> {
+        self.contract.view(Self::encode_balance_of(account, id)?)
+    }
+
+    /// Returns the balances of the pairs of the accounts and the tokens.
+    pub fn balance_of_batch(&self, accounts: &[Address], ids: &[U256]) -> Result<Vec<U256>> {
+        self.contract
+            .view(Self::encode_balance_of_batch(accounts, ids)?)
+    }
+
+    /// Returns the URI of the metadata of the token, where `{id}` is to be
+    /// replaced with the id of the token in hex.
+    pub fn uri(&self, id: U256) -> Result<String> {
+        self.contract.view(Self::encode_uri(id)?)
+    }
+
+    /// Returns true if `operator` is allowed to transfer all the tokens of
+    /// `account`.
+    pub fn is_approved_for_all(&self, account: Address, operator: Address) -> Result<bool> {
+        self.contract
+            .view(Self::encode_is_approved_for_all(account, operator)?)
+    }
+
+    /// Mints `amount` of the token `id` to `to`, and returns the hash of the
+    /// transaction.
+    pub fn mint(&self, to: Address, id: U256, amount: U256, data: &[u8]) -> Result<H256> {
+        self.contract.send(Self::encode_mint(to, id, amount, data)?)
+    }
+
+    /// Transfers `amount` of the token `id` of `from` to `to`.
+    pub fn safe_transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+        data: &[u8],
+    ) -> Result<H256> {
+        self.contract
+            .send(Self::encode_safe_transfer_from(from, to, id, amount, data)?)
+    }
+
+    /// Transfers the amounts of the tokens of `from` to `to`.
+    pub fn safe_batch_transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        ids: &[U256],
+        amounts: &[U256],
+        data: &[u8],
+    ) -> Result<H256> {
+        self.contract.send(Self::encode_safe_batch_transfer_from(
+            from, to, ids, amounts, data,
+        )?)
+    }
+
+    /// Allows or disallows `operator` to transfer all the tokens of the
+    /// operator.
+    pub fn set_approval_for_all(&self, operator: Address, approved: bool) -> Result<H256> {
+        self.contract
+            .send(Self::encode_set_approval_for_all(operator, approved)?)
+    }
+
+    /// Encodes the calldata of `balanceOf(address,uint256)`.
+    pub fn encode_balance_of(account: Address, id: U256) -> Result<Vec<u8>> {
+        encode_call(
+            "balanceOf",
+            &["address", "uint256"],
+            vec![account.into_token(), id.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `balanceOfBatch(address[],uint256[])`.
+    pub fn encode_balance_of_batch(accounts: &[Address], ids: &[U256]) -> Result<Vec<u8>> {
+        if accounts.len() != ids.len() {
+            bail!(
+                "expected the ids of {} accounts, found {}",
+                accounts.len(),
+                ids.len()
+            );
+        }
+        encode_call(
+            "balanceOfBatch",
+            &["address[]", "uint256[]"],
+            vec![accounts.to_vec().into_token(), ids.to_vec().into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `uri(uint256)`.
+    pub fn encode_uri(id: U256) -> Result<Vec<u8>> {
+        encode_call("uri", &["uint256"], vec![id.into_token()])
+    }
+
+    /// Encodes the calldata of `isApprovedForAll(address,address)`.
+    pub fn encode_is_approved_for_all(account: Address, operator: Address) -> Result<Vec<u8>> {
+        encode_call(
+            "isApprovedForAll",
+            &["address", "address"],
+            vec![account.into_token(), operator.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `mint(address,uint256,uint256,bytes)`.
+    pub fn encode_mint(to: Address, id: U256, amount: U256, data: &[u8]) -> Result<Vec<u8>> {
+        encode_call(
+            "mint",
+            &["address", "uint256", "uint256", "bytes"],
+            vec![
+                to.into_token(),
+                id.into_token(),
+                amount.into_token(),
+                data.to_vec().into_token(),
+            ],
+        )
+    }
+
+    /// Encodes the calldata of
+    /// `safeTransferFrom(address,address,uint256,uint256,bytes)`.
+    pub fn encode_safe_transfer_from(
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+        data: &[u8],
+    ) -> Result<Vec<u8>> {
+        encode_call(
+            "safeTransferFrom",
+            &["address", "address", "uint256", "uint256", "bytes"],
+            vec![
+                from.into_token(),
+                to.into_token(),
+                id.into_token(),
+                amount.into_token(),
+                data.to_vec().into_token(),
+            ],
+        )
+    }
+
+    /// Encodes the calldata of
+    /// `safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)`.
+    pub fn encode_safe_batch_transfer_from(
+        from: Address,
+        to: Address,
+        ids: &[U256],
+        amounts: &[U256],
+        data: &[u8],
+    ) -> Result<Vec<u8>> {
+        if ids.len() != amounts.len() {
+            bail!(
+                "expected the amounts of {} tokens, found {}",
+                ids.len(),
+                amounts.len()
+            );
+        }
+        encode_call(
+            "safeBatchTransferFrom",
+            &["address", "address", "uint256[]", "uint256[]", "bytes"],
+            vec![
+                from.into_token(),
+                to.into_token(),
+                ids.to_vec().into_token(),
+                amounts.to_vec().into_token(),
+                data.to_vec().into_token(),
+            ],
+        )
+    }
+
+    /// Encodes the calldata of `setApprovalForAll(address,bool)`.
+    pub fn encode_set_approval_for_all(operator: Address, approved: bool) -> Result<Vec<u8>> {
+        encode_call(
+            "setApprovalForAll",
+            &["address", "bool"],
+            vec![operator.into_token(), approved.into_token()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::contract::selector;
+    use super::*;
+
+    #[test]
+    fn test_erc1155() {
+        let to = Address::from([0x11; 20]);
+        let id = U256::from(7u64);
+        assert_eq!(
+            selector(Erc1155::encode_balance_of(to, id).unwrap()),
+            "00fdd58e"
+        );
+        assert_eq!(
+            selector(Erc1155::encode_balance_of_batch(&[to], &[id]).unwrap()),
+            "4e1273f4"
+        );
+        assert_eq!(
+            selector(Erc1155::encode_safe_transfer_from(to, to, id, U256::ONE, &[]).unwrap()),
+            "f242432a"
+        );
+        assert_eq!(
+            selector(
+                Erc1155::encode_safe_batch_transfer_from(to, to, &[id], &[U256::ONE], &[]).unwrap()
+            ),
+            "2eb2c2d6"
+        );
+        assert_eq!(
+            selector(Erc1155::encode_mint(to, id, U256::ONE, &[]).unwrap()),
+            "731133e9"
+        );
+        assert!(Erc1155::encode_balance_of_batch(&[to, to], &[id]).is_err());
+        assert!(Erc1155::encode_safe_batch_transfer_from(to, to, &[id], &[], &[]).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use super::contract::Contract;
+use crate::blockchain::abi::{encode_call, Tokenizable};
+use crate::blockchain::types::{Address, H256, U256};
+use anyhow::Result;
+use std::cell::OnceCell;
+
+/// A client of an [ERC-20] token.
+///
+/// The amounts are in the smallest unit of the token, which are converted
+/// from and into the amounts in the decimals of the token by
+/// [`parse_amount`](Erc20::parse_amount) and
+/// [`format_amount`](Erc20::format_amount). The decimals are read from the
+/// contract once, unless they're given by
+/// [`with_decimals`](Erc20::with_decimals).
+///
+/// [ERC-20]: https://eips.ethereum.org/EIPS/eip-20
+#[derive(Debug, Clone)]
+pub struct Erc20 {
+    contract: Contract,
+    decimals: OnceCell<u8>,
+}
+
+impl Erc20 {
+    /// Creates a client of the token at `address` on the chain.
+    pub fn new(chain_id: u32, address: Address) -> Self {
+        Erc20 {
+            contract: Contract { chain_id, address },
+            decimals: OnceCell::new(),
+        }
+    }
+
+    /// Sets the decimals of the token, without reading them from the
+    /// contract.
+    pub fn with_decimals(self, decimals: u8) -> Self {
+        Erc20 {
+            decimals: OnceCell::from(decimals),
+            ..self
+        }
+    }
+
+    /// Returns the id of the chain of the token.
+    pub fn chain_id(&self) -> u32 {
+        self.contract.chain_id
+    }
+
+    /// Returns the address of the token contract.
+    pub fn address(&self) -> Address {
+        self.contract.address
+    }
+
+    /// Returns the name of the token.
+    pub fn name(&self) -> Result<String> {
+        self.contract.view(Self::encode_name()?)
+    }
+
+    /// Returns the symbol of the token.
+    pub fn symbol(&self) -> Result<String> {
+        self.contract.view(Self::encode_symbol()?)
+    }
+
+    /// Returns the decimals of the token.
+    pub fn decimals(&self) -> Result<u8> {
+        if let Some(decimals) = self.decimals.get() {
+            return Ok(*decimals);
+        }
+        let decimals = self.contract.view(Self::encode_decimals()?)?;
+        Ok(*self.decimals.get_or_init(|| decimals))
+    }
+
+    /// Returns the total supply of the token.
+    pub fn total_supply(&self) -> Result<U256> {
+        self.contract.view(Self::encode_total_supply()?)
+    }
+
+    /// Returns the balance of `owner`.
+    pub fn balance_of(&self, owner: Address) -> Result<U256> {
+        self.contract.view(Self::encode_balance_of(owner)?)
+    }
+
+    /// Returns the amount which `spender` is allowed to transfer from
+    /// `owner`.
+    pub fn allowance(&self, owner: Address, spender: Address) -> Result<U256> {
+        self.contract.view(Self::encode_allowance(owner, spender)?)
+    }
+
+    /// Transfers `amount` of the operator to `to`, and returns the hash of
+    /// the transaction.
+    pub fn transfer(&self, to: Address, amount: U256) -> Result<H256> {
+        self.contract.send(Self::encode_transfer(to, amount)?)
+    }
+
+    /// Allows `spender` to transfer `amount` of the operator.
+    pub fn approve(&self, spender: Address, amount: U256) -> Result<H256> {
+        self.contract.send(Self::encode_approve(spender, amount)?)
+    }
+
+    /// Transfers `amount` of `from` to `to` with the allowance of the
+    /// operator.
+    pub fn transfer_from(&self, from: Address, to: Address, amount: U256) -> Result<H256> {
+        self.contract
+            .send(Self::encode_transfer_from(from, to, amount)?)
+    }
+
+    /// Parses an amount in the decimals of the token into its smallest unit,
+    /// e.g. `"1.5"` into `1500000` of a token with 6 decimals.
+    pub fn parse_amount(&self, amount: &str) -> Result<U256> {
+        U256::parse_units(amount, self.decimals()? as u32)
+    }
+
+    /// Formats an amount in the smallest unit of the token in its decimals.
+    pub fn format_amount(&self, amount: &U256) -> Result<String> {
+        Ok(amount.format_units(self.decimals()? as u32))
+    }
+
+    /// Encodes the calldata of `name()`.
+    pub fn encode_name() -> Result<Vec<u8>> {
+        encode_call("name", &[], vec![])
+    }
+
+    /// Encodes the calldata of `symbol()`.
+    pub fn encode_symbol() -> Result<Vec<u8>> {
+        encode_call("symbol", &[], vec![])
+    }
+
+    /// Encodes the calldata of `decimals()`.
+    pub fn encode_decimals() -> Result<Vec<u8>> {
+        encode_call("decimals", &[], vec![])
+    }
+
+    /// Encodes the calldata of `totalSupply()`.
+    pub fn encode_total_supply() -> Result<Vec<u8>> {
+        encode_call("totalSupply", &[], vec![])
+    }
+
+    /// Encodes the calldata of `balanceOf(address)`.
+    pub fn encode_balance_of(owner: Address) -> Result<Vec<u8>> {
+        encode_call("balanceOf", &["address"], vec![owner.into_token()])
+    }
+
+    /// Encodes the calldata of `allowance(address,address)`.
+    pub fn encode_allowance(owner: Address, spender: Address) -> Result<Vec<u8>> {
+        encode_call(
+            "allowance",
+            &["address", "address"],
+            vec![owner.into_token(), spender.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `transfer(address,uint256)`.
+    pub fn encode_transfer(to: Address, amount: U256) -> Result<Vec<u8>> {
+        encode_call(
+            "transfer",
+            &["address", "uint256"],
+            vec![to.into_token(), amount.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `approve(address,uint256)`.
+    pub fn encode_approve(spender: Address, amount: U256) -> Result<Vec<u8>> {
+        encode_call(
+            "approve",
+            &["address", "uint256"],
+            vec![spender.into_token(), amount.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `transferFrom(address,address,uint256)`.
+    pub fn encode_transfer_from(from: Address, to: Address, amount: U256) -> Result<Vec<u8>> {
+        encode_call(
+            "transferFrom",
+            &["address", "address", "uint256"],
+            vec![from.into_token(), to.into_token(), amount.into_token()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::contract::selector;
+    use super::*;
+
+    #[test]
+    fn test_erc20() {
+        let to = Address::from([0x11; 20]);
+        let data = Erc20::encode_transfer(to, U256::from(1000u64)).unwrap();
+        assert_eq!(selector(data.clone()), "a9059cbb");
+        assert_eq!(&data[16..36], to.as_bytes());
+        assert_eq!(
+            U256::from_big_endian(&data[36..]).unwrap(),
+            U256::from(1000u64)
+        );
+        assert_eq!(
+            selector(Erc20::encode_approve(to, U256::MAX).unwrap()),
+            "095ea7b3"
+        );
+        assert_eq!(
+            hex::encode(Erc20::encode_balance_of(to).unwrap()),
+            "70a08231".to_string() + &"0".repeat(24) + &"11".repeat(20)
+        );
+        assert_eq!(hex::encode(Erc20::encode_decimals().unwrap()), "313ce567");
+        assert_eq!(
+            selector(Erc20::encode_transfer_from(to, to, U256::ONE).unwrap()),
+            "23b872dd"
+        );
+
+        // the decimals aren't read from the contract once given
+        let token = Erc20::new(4690, to).with_decimals(6);
+        assert_eq!(token.decimals.get(), Some(&6));
+    }
+}
@@ -0,0 +1,237 @@
+use super::contract::Contract;
+use crate::blockchain::abi::{encode_call, Tokenizable};
+use crate::blockchain::types::{Address, H256, U256};
+use anyhow::Result;
+
+/// A client of an [ERC-721] token, i.e. an NFT.
+///
+/// Besides the standard functions, it mints the tokens by `safeMint` of the
+/// usual mintable contracts, e.g. by OpenZeppelin, which is only allowed to
+/// the minter.
+///
+/// [ERC-721]: https://eips.ethereum.org/EIPS/eip-721
+#[derive(Debug, Clone)]
+pub struct Erc721 {
+    contract: Contract,
+}
+
+impl Erc721 {
+    /// Creates a client of the token at `address` on the chain.
+    pub fn new(chain_id: u32, address: Address) -> Self {
+        Erc721 {
+            contract: Contract { chain_id, address },
+        }
+    }
+
+    /// Returns the id of the chain of the token.
+    pub fn chain_id(&self) -> u32 {
+        self.contract.chain_id
+    }
+
+    /// Returns the address of the token contract.
+    pub fn address(&self) -> Address {
+        self.contract.address
+    }
+
+    /// Returns the name of the token.
+    pub fn name(&self) -> Result<String> {
+        self.contract.view(Self::encode_name()?)
+    }
+
+    /// Returns the symbol of the token.
+    pub fn symbol(&self) -> Result<String> {
+        self.contract.view(Self::encode_symbol()?)
+    }
+
+    /// Returns the number of the tokens of `owner`.
+    pub fn balance_of(&self, owner: Address) -> Result<U256> {
+        self.contract.view(Self::encode_balance_of(owner)?)
+    }
+
+    /// Returns the owner of the token.
+    pub fn owner_of(&self, token_id: U256) -> Result<Address> {
+        self.contract.view(Self::encode_owner_of(token_id)?)
+    }
+
+    /// Returns the URI of the metadata of the token.
+    pub fn token_uri(&self, token_id: U256) -> Result<String> {
+        self.contract.view(Self::encode_token_uri(token_id)?)
+    }
+
+    /// Returns the address approved to transfer the token.
+    pub fn get_approved(&self, token_id: U256) -> Result<Address> {
+        self.contract.view(Self::encode_get_approved(token_id)?)
+    }
+
+    /// Returns true if `operator` is allowed to transfer all the tokens of
+    /// `owner`.
+    pub fn is_approved_for_all(&self, owner: Address, operator: Address) -> Result<bool> {
+        self.contract
+            .view(Self::encode_is_approved_for_all(owner, operator)?)
+    }
+
+    /// Mints the token to `to`, and returns the hash of the transaction.
+    pub fn safe_mint(&self, to: Address, token_id: U256) -> Result<H256> {
+        self.contract.send(Self::encode_safe_mint(to, token_id)?)
+    }
+
+    /// Mints the token with the URI of its metadata to `to`.
+    pub fn safe_mint_with_uri(&self, to: Address, token_id: U256, uri: &str) -> Result<H256> {
+        self.contract
+            .send(Self::encode_safe_mint_with_uri(to, token_id, uri)?)
+    }
+
+    /// Transfers the token of `from` to `to`, which is checked to receive
+    /// ERC-721 tokens if it's a contract.
+    pub fn safe_transfer_from(&self, from: Address, to: Address, token_id: U256) -> Result<H256> {
+        self.contract
+            .send(Self::encode_safe_transfer_from(from, to, token_id)?)
+    }
+
+    /// Transfers the token of `from` to `to`.
+    pub fn transfer_from(&self, from: Address, to: Address, token_id: U256) -> Result<H256> {
+        self.contract
+            .send(Self::encode_transfer_from(from, to, token_id)?)
+    }
+
+    /// Allows `to` to transfer the token of the operator.
+    pub fn approve(&self, to: Address, token_id: U256) -> Result<H256> {
+        self.contract.send(Self::encode_approve(to, token_id)?)
+    }
+
+    /// Allows or disallows `operator` to transfer all the tokens of the
+    /// operator.
+    pub fn set_approval_for_all(&self, operator: Address, approved: bool) -> Result<H256> {
+        self.contract
+            .send(Self::encode_set_approval_for_all(operator, approved)?)
+    }
+
+    /// Encodes the calldata of `name()`.
+    pub fn encode_name() -> Result<Vec<u8>> {
+        encode_call("name", &[], vec![])
+    }
+
+    /// Encodes the calldata of `symbol()`.
+    pub fn encode_symbol() -> Result<Vec<u8>> {
+        encode_call("symbol", &[], vec![])
+    }
+
+    /// Encodes the calldata of `balanceOf(address)`.
+    pub fn encode_balance_of(owner: Address) -> Result<Vec<u8>> {
+        encode_call("balanceOf", &["address"], vec![owner.into_token()])
+    }
+
+    /// Encodes the calldata of `ownerOf(uint256)`.
+    pub fn encode_owner_of(token_id: U256) -> Result<Vec<u8>> {
+        encode_call("ownerOf", &["uint256"], vec![token_id.into_token()])
+    }
+
+    /// Encodes the calldata of `tokenURI(uint256)`.
+    pub fn encode_token_uri(token_id: U256) -> Result<Vec<u8>> {
+        encode_call("tokenURI", &["uint256"], vec![token_id.into_token()])
+    }
+
+    /// Encodes the calldata of `getApproved(uint256)`.
+    pub fn encode_get_approved(token_id: U256) -> Result<Vec<u8>> {
+        encode_call("getApproved", &["uint256"], vec![token_id.into_token()])
+    }
+
+    /// Encodes the calldata of `isApprovedForAll(address,address)`.
+    pub fn encode_is_approved_for_all(owner: Address, operator: Address) -> Result<Vec<u8>> {
+        encode_call(
+            "isApprovedForAll",
+            &["address", "address"],
+            vec![owner.into_token(), operator.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `safeMint(address,uint256)`.
+    pub fn encode_safe_mint(to: Address, token_id: U256) -> Result<Vec<u8>> {
+        encode_call(
+            "safeMint",
+            &["address", "uint256"],
+            vec![to.into_token(), token_id.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `safeMint(address,uint256,string)`.
+    pub fn encode_safe_mint_with_uri(to: Address, token_id: U256, uri: &str) -> Result<Vec<u8>> {
+        encode_call(
+            "safeMint",
+            &["address", "uint256", "string"],
+            vec![
+                to.into_token(),
+                token_id.into_token(),
+                uri.to_string().into_token(),
+            ],
+        )
+    }
+
+    /// Encodes the calldata of `safeTransferFrom(address,address,uint256)`.
+    pub fn encode_safe_transfer_from(
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<Vec<u8>> {
+        encode_call(
+            "safeTransferFrom",
+            &["address", "address", "uint256"],
+            vec![from.into_token(), to.into_token(), token_id.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `transferFrom(address,address,uint256)`.
+    pub fn encode_transfer_from(from: Address, to: Address, token_id: U256) -> Result<Vec<u8>> {
+        encode_call(
+            "transferFrom",
+            &["address", "address", "uint256"],
+            vec![from.into_token(), to.into_token(), token_id.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `approve(address,uint256)`.
+    pub fn encode_approve(to: Address, token_id: U256) -> Result<Vec<u8>> {
+        encode_call(
+            "approve",
+            &["address", "uint256"],
+            vec![to.into_token(), token_id.into_token()],
+        )
+    }
+
+    /// Encodes the calldata of `setApprovalForAll(address,bool)`.
+    pub fn encode_set_approval_for_all(operator: Address, approved: bool) -> Result<Vec<u8>> {
+        encode_call(
+            "setApprovalForAll",
+            &["address", "bool"],
+            vec![operator.into_token(), approved.into_token()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::contract::selector;
+    use super::*;
+
+    #[test]
+    fn test_erc721() {
+        let to = Address::from([0x11; 20]);
+        let id = U256::from(7u64);
+        assert_eq!(selector(Erc721::encode_owner_of(id).unwrap()), "6352211e");
+        assert_eq!(selector(Erc721::encode_token_uri(id).unwrap()), "c87b56dd");
+        assert_eq!(
+            selector(Erc721::encode_safe_mint(to, id).unwrap()),
+            "a1448194"
+        );
+        assert_eq!(
+            selector(Erc721::encode_safe_transfer_from(to, to, id).unwrap()),
+            "42842e0e"
+        );
+        assert_eq!(
+            selector(Erc721::encode_set_approval_for_all(to, true).unwrap()),
+            "a22cb465"
+        );
+        let data = Erc721::encode_safe_mint_with_uri(to, id, "ipfs://x").unwrap();
+        assert_eq!(data.len(), 4 + 32 * 5);
+    }
+}
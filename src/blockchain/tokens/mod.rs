@@ -0,0 +1,31 @@
+//! Typed clients of the standard token contracts.
+//!
+//! The clients of [ERC-20], [ERC-721] and [ERC-1155] read the contracts by
+//! [`call_contract`](super::call_contract) and write them by
+//! [`send_tx`](super::send_tx). Each method has an `encode_*` counterpart
+//! returning its calldata, e.g. to batch the reads by
+//! [`Multicall`](super::multicall::Multicall).
+//!
+//! ```no_run
+//! use ws_sdk::blockchain::tokens::Erc20;
+//!
+//! let token = Erc20::new(4690, "0x3908c0620ABC34a23A078097e2e878AFf60bbC28".parse()?);
+//! let device = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse()?;
+//! let reward = token.parse_amount("1.5")?;
+//! let hash = token.transfer(device, reward)?;
+//! let balance = token.format_amount(&token.balance_of(device)?)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! It's enabled by the `abi` feature flag.
+//!
+//! [ERC-20]: https://eips.ethereum.org/EIPS/eip-20
+//! [ERC-721]: https://eips.ethereum.org/EIPS/eip-721
+//! [ERC-1155]: https://eips.ethereum.org/EIPS/eip-1155
+mod contract;
+mod erc1155;
+pub use self::erc1155::*;
+mod erc20;
+pub use self::erc20::*;
+mod erc721;
+pub use self::erc721::*;
@@ -0,0 +1,216 @@
+use super::types::{Address, H256, U256};
+use crate::host::abi::*;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_with::skip_serializing_none;
+
+/// An entry of the [EIP-2930] access list: an address with its storage keys.
+///
+/// [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    /// The address of the contract accessed.
+    pub address: Address,
+    /// The storage slots of the contract accessed.
+    pub storage_keys: Vec<H256>,
+}
+
+/// A transaction sent through the node operator, with the optional fields
+/// overriding the ones picked by the node.
+///
+/// The amounts, i.e. the value and the fees, are in wei.
+/// Either the legacy gas price or the [EIP-1559] fees can be set, not both.
+///
+/// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+///
+/// # Examples
+///
+/// Replace a stuck transaction by sending it again with the same nonce and
+/// higher fees:
+///
+/// ```no_run
+/// use ws_sdk::blockchain::{types::U256, Chain, TxRequest};
+/// let hash = TxRequest::new("0x83c9fb5da807e4427b59b6c90b545496394abf98".parse()?)
+///     .data("60fe47b1000000000000000000000000000000000000000000000000000000000000002a")
+///     .gas_limit(100_000)
+///     .max_fee_per_gas(U256::from_gwei("60")?)
+///     .max_priority_fee_per_gas(U256::from_gwei("3")?)
+///     .nonce(42)
+///     .send(Chain::IotexTestnet.id())?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[skip_serializing_none]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxRequest {
+    to: Address,
+    data: String,
+    value: U256,
+    gas: Option<u64>,
+    gas_price: Option<U256>,
+    max_fee_per_gas: Option<U256>,
+    max_priority_fee_per_gas: Option<U256>,
+    nonce: Option<u64>,
+    access_list: Option<Vec<AccessListItem>>,
+    operator: Option<String>,
+}
+
+impl TxRequest {
+    /// Creates a transaction to `to`, without value and data.
+    pub fn new(to: Address) -> Self {
+        TxRequest {
+            to,
+            data: String::new(),
+            value: U256::ZERO,
+            gas: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: None,
+            access_list: None,
+            operator: None,
+        }
+    }
+
+    /// Sets the value in wei.
+    pub fn value(mut self, wei: U256) -> Self {
+        self.value = wei;
+        self
+    }
+
+    /// Sets the hex encoded calldata.
+    pub fn data(mut self, data: &str) -> Self {
+        self.data = data.to_owned();
+        self
+    }
+
+    /// Sets the calldata, which is hex encoded.
+    pub fn calldata(self, data: &[u8]) -> Self {
+        self.data(&hex::encode(data))
+    }
+
+    /// Sets the gas limit.
+    pub fn gas_limit(mut self, gas: u64) -> Self {
+        self.gas = Some(gas);
+        self
+    }
+
+    /// Sets the legacy gas price in wei.
+    pub fn gas_price(mut self, wei: U256) -> Self {
+        self.gas_price = Some(wei);
+        self
+    }
+
+    /// Sets the EIP-1559 max fee per gas in wei.
+    pub fn max_fee_per_gas(mut self, wei: U256) -> Self {
+        self.max_fee_per_gas = Some(wei);
+        self
+    }
+
+    /// Sets the EIP-1559 max priority fee per gas in wei.
+    pub fn max_priority_fee_per_gas(mut self, wei: U256) -> Self {
+        self.max_priority_fee_per_gas = Some(wei);
+        self
+    }
+
+    /// Sets the nonce explicitly, e.g. to replace a pending transaction.
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Adds an address with its storage keys to the access list.
+    pub fn access(mut self, address: Address, storage_keys: &[H256]) -> Self {
+        self.access_list
+            .get_or_insert_with(Vec::new)
+            .push(AccessListItem {
+                address,
+                storage_keys: storage_keys.to_vec(),
+            });
+        self
+    }
+
+    /// Sets the access list.
+    pub fn access_list(mut self, list: Vec<AccessListItem>) -> Self {
+        self.access_list = Some(list);
+        self
+    }
+
+    /// Selects the account of the node operator signing the transaction by
+    /// its name, instead of the default one of the project.
+    pub fn operator(mut self, name: &str) -> Self {
+        self.operator = Some(name.to_owned());
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.gas_price.is_some()
+            && (self.max_fee_per_gas.is_some() || self.max_priority_fee_per_gas.is_some())
+        {
+            bail!("the gas price can't be set with the max fees of EIP-1559");
+        }
+        if let (Some(max), Some(priority)) = (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+            if priority > max {
+                bail!("the max priority fee per gas is more than the max fee per gas");
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the transaction to the blockchain whose chain id is `chain_id`,
+    /// and returns the hash of the transaction.
+    pub fn send(&self, chain_id: u32) -> Result<H256> {
+        self.validate()?;
+        let str = serde_json::to_string(self)?;
+        let data_ptr = &mut 0_i32 as *const _ as *const *mut u8;
+        let data_size = &mut 0_i32 as *const i32;
+        match unsafe {
+            ws_send_tx(
+                chain_id as _,
+                str.as_ptr(),
+                str.len() as _,
+                data_ptr,
+                data_size,
+            )
+        } {
+            0 => unsafe { String::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) }
+                .parse()
+                .context("invalid tx hash returned by the host"),
+            _ => bail!("fail to send tx"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tx_request() {
+        let to: Address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            .parse()
+            .unwrap();
+        let tx = TxRequest::new(to)
+            .value(U256::from(10u64))
+            .gas_limit(21000)
+            .max_fee_per_gas(U256::from(200u64))
+            .max_priority_fee_per_gas(U256::from(30u64))
+            .access(Address::ZERO, &[H256::ZERO]);
+        assert!(tx.validate().is_ok());
+        assert_eq!(
+            serde_json::to_string(&tx).unwrap(),
+            format!(
+                r#"{{"to":"{}","data":"","value":"10","gas":21000,"maxFeePerGas":"200","maxPriorityFeePerGas":"30","accessList":[{{"address":"{}","storageKeys":["{}"]}}]}}"#,
+                to,
+                Address::ZERO,
+                H256::ZERO
+            )
+        );
+        assert!(tx.clone().gas_price(U256::ONE).validate().is_err());
+        assert!(tx
+            .max_priority_fee_per_gas(U256::from(201u64))
+            .validate()
+            .is_err());
+    }
+}
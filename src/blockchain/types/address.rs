@@ -0,0 +1,169 @@
+use super::keccak256;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A 20-byte account or contract address.
+///
+/// It's formatted with the [EIP-55] mixed-case checksum. When parsed from a
+/// mixed-case string, the checksum is verified, so a typo is caught before
+/// the address is sent to the node; an all-lowercase or all-uppercase string
+/// carries no checksum and is accepted as is.
+///
+/// [EIP-55]: https://eips.ethereum.org/EIPS/eip-55
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::types::Address;
+/// let addr: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse()?;
+/// assert_eq!(addr.to_string(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+/// assert!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".parse::<Address>().is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Address([u8; 20]);
+
+impl Address {
+    /// The zero address.
+    pub const ZERO: Address = Address([0; 20]);
+
+    /// Creates an address from its bytes, e.g. for a constant.
+    pub const fn new(bytes: [u8; 20]) -> Self {
+        Address(bytes)
+    }
+
+    /// Creates an address from 20 bytes.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        match bytes.try_into() {
+            Ok(bytes) => Ok(Address(bytes)),
+            Err(_) => bail!("an address has 20 bytes, found {}", bytes.len()),
+        }
+    }
+
+    /// Returns the bytes of the address.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// Formats the address with the EIP-55 checksum.
+    pub fn to_checksum(&self) -> String {
+        let lower = hex::encode(self.0);
+        let hash = keccak256(lower.as_bytes());
+        let mut out = String::with_capacity(42);
+        out.push_str("0x");
+        for (i, c) in lower.chars().enumerate() {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0xf;
+            if nibble >= 8 {
+                out.extend(c.to_uppercase());
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(bytes: [u8; 20]) -> Self {
+        Address(bytes)
+    }
+}
+
+impl From<Address> for [u8; 20] {
+    fn from(addr: Address) -> Self {
+        addr.0
+    }
+}
+
+impl AsRef<[u8]> for Address {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl FromStr for Address {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        if digits.len() != 40 {
+            bail!("invalid address `{}`: expected 40 hex digits", s);
+        }
+        let mut bytes = [0; 20];
+        if hex::decode_to_slice(digits, &mut bytes).is_err() {
+            bail!("invalid address `{}`: not a hex string", s);
+        }
+        let addr = Address(bytes);
+        let mixed = digits.chars().any(|c| c.is_ascii_lowercase())
+            && digits.chars().any(|c| c.is_ascii_uppercase());
+        if mixed && addr.to_checksum()[2..] != *digits {
+            bail!("invalid address `{}`: wrong checksum", s);
+        }
+        Ok(addr)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+impl fmt::LowerHex for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_checksum())
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        // the test vectors of EIP-55
+        for s in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let addr: Address = s.parse().unwrap();
+            assert_eq!(addr.to_string(), s);
+            assert_eq!(s.to_lowercase().parse::<Address>().unwrap(), addr);
+        }
+        assert!("0xfb6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+            .parse::<Address>()
+            .is_err());
+        assert!("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea"
+            .parse::<Address>()
+            .is_err());
+        assert!("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beagg"
+            .parse::<Address>()
+            .is_err());
+    }
+}
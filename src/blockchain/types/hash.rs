@@ -0,0 +1,93 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A 32-byte hash, e.g. of a transaction or a block.
+///
+/// It's formatted as a `0x` prefixed lowercase hex string.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::types::H256;
+/// let hash: H256 = "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b".parse()?;
+/// assert_eq!(hash.as_bytes()[0], 0x88);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct H256([u8; 32]);
+
+impl H256 {
+    /// The zero hash.
+    pub const ZERO: H256 = H256([0; 32]);
+
+    /// Creates a hash from 32 bytes.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        match bytes.try_into() {
+            Ok(bytes) => Ok(H256(bytes)),
+            Err(_) => bail!("a hash has 32 bytes, found {}", bytes.len()),
+        }
+    }
+
+    /// Returns the bytes of the hash.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for H256 {
+    fn from(bytes: [u8; 32]) -> Self {
+        H256(bytes)
+    }
+}
+
+impl From<H256> for [u8; 32] {
+    fn from(hash: H256) -> Self {
+        hash.0
+    }
+}
+
+impl AsRef<[u8]> for H256 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl FromStr for H256 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        let mut bytes = [0; 32];
+        if digits.len() != 64 || hex::decode_to_slice(digits, &mut bytes).is_err() {
+            bail!("invalid hash `{}`: expected 64 hex digits", s);
+        }
+        Ok(H256(bytes))
+    }
+}
+
+impl fmt::Display for H256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for H256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Serialize for H256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for H256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
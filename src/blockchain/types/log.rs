@@ -0,0 +1,29 @@
+use super::serde_helpers::{hex_bytes, opt_quantity};
+use super::{Address, H256};
+use serde::Deserialize;
+
+/// A log emitted by a contract, as returned by the node.
+///
+/// The first topic is the hash of the event signature, unless the event is
+/// anonymous, followed by the indexed parameters; `data` holds the ABI
+/// encoded non-indexed parameters.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    #[serde(with = "hex_bytes")]
+    pub data: Vec<u8>,
+    #[serde(default, with = "opt_quantity")]
+    pub block_number: Option<u64>,
+    #[serde(default)]
+    pub block_hash: Option<H256>,
+    #[serde(default)]
+    pub transaction_hash: Option<H256>,
+    #[serde(default, with = "opt_quantity")]
+    pub transaction_index: Option<u64>,
+    #[serde(default, with = "opt_quantity")]
+    pub log_index: Option<u64>,
+    #[serde(default)]
+    pub removed: bool,
+}
@@ -0,0 +1,21 @@
+//! The typed values of the blockchain: [`Address`], [`U256`], [`H256`] and
+//! the [`Log`]s of the contracts.
+//!
+//! They're validated when parsed, so a malformed address or amount is caught
+//! in the wasm instead of being sent to the node.
+use sha3::{Digest, Keccak256};
+
+mod address;
+pub use self::address::*;
+mod hash;
+pub use self::hash::*;
+mod uint;
+pub use self::uint::*;
+mod log;
+pub use self::log::*;
+pub(crate) mod serde_helpers;
+
+/// Computes the Keccak-256 hash of `data`, as used by Ethereum.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
@@ -0,0 +1,53 @@
+// The JSON-RPC encodings of the nodes: a quantity is a `0x` prefixed hex
+// string, though some hosts send it as a number or in decimal, and the bytes
+// are `0x` prefixed hex strings.
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Quantity {
+    Number(u64),
+    String(String),
+}
+
+fn parse_quantity<E: Error>(q: Quantity) -> Result<u64, E> {
+    match q {
+        Quantity::Number(n) => Ok(n),
+        Quantity::String(s) => match s.strip_prefix("0x") {
+            Some(h) => u64::from_str_radix(h, 16),
+            None => s.parse(),
+        }
+        .map_err(|_| E::custom(format!("invalid quantity `{}`", s))),
+    }
+}
+
+pub(crate) mod quantity {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        parse_quantity(Quantity::deserialize(deserializer)?)
+    }
+}
+
+pub(crate) mod opt_quantity {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u64>, D::Error> {
+        Option::<Quantity>::deserialize(deserializer)?
+            .map(parse_quantity)
+            .transpose()
+    }
+}
+
+pub(crate) mod hex_bytes {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s.strip_prefix("0x").unwrap_or(&s))
+            .map_err(|_| D::Error::custom(format!("invalid hex bytes `{}`", s)))
+    }
+}
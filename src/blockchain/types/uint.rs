@@ -0,0 +1,442 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Rem, Sub, SubAssign};
+use std::str::FromStr;
+
+mod inner {
+    #![allow(clippy::all)]
+    uint::construct_uint! {
+        pub struct Inner(4);
+    }
+}
+use inner::Inner;
+
+/// The decimals of the ether units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Wei,
+    Gwei,
+    Ether,
+}
+
+impl Unit {
+    /// Returns the number of decimals of the unit relative to wei.
+    pub fn decimals(&self) -> u32 {
+        match self {
+            Unit::Wei => 0,
+            Unit::Gwei => 9,
+            Unit::Ether => 18,
+        }
+    }
+}
+
+/// A 256-bit unsigned integer, e.g. an amount in wei.
+///
+/// It's parsed from a decimal string, a `0x` prefixed hex string, or a
+/// decimal amount with an ether unit, e.g. `"1.5 ether"` or `"20 gwei"`, so
+/// a decimal string is never taken as hex by mistake. It's formatted in
+/// decimal, and serialized as a decimal string.
+///
+/// The arithmetic operators panic on overflow, see the `checked_*` methods
+/// for the fallible ones.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::types::{Unit, U256};
+/// let value: U256 = "1.5 ether".parse()?;
+/// assert_eq!(value, U256::from_ether("1.5")?);
+/// assert_eq!(value.to_string(), "1500000000000000000");
+/// assert_eq!(value.format_units(Unit::Gwei.decimals()), "1500000000");
+/// assert_eq!("0x10".parse::<U256>()?, U256::from(16u64));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct U256(Inner);
+
+impl U256 {
+    /// Zero.
+    pub const ZERO: U256 = U256(Inner([0; 4]));
+    /// One.
+    pub const ONE: U256 = U256(Inner([1, 0, 0, 0]));
+    /// The maximum value, `2^256 - 1`.
+    pub const MAX: U256 = U256(Inner([u64::MAX; 4]));
+    /// The most decimals of a unit, as `10^77` is the largest power of ten in
+    /// 256 bits.
+    pub const MAX_DECIMALS: u32 = 77;
+
+    /// Parses a decimal string.
+    pub fn from_dec_str(s: &str) -> Result<Self> {
+        Inner::from_dec_str(s)
+            .map(U256)
+            .map_err(|e| anyhow::anyhow!("invalid decimal integer `{}`: {:?}", s, e))
+    }
+
+    /// Parses a hex string, with or without the `0x` prefix.
+    pub fn from_hex_str(s: &str) -> Result<Self> {
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        if digits.is_empty() || digits.len() > 64 {
+            bail!("invalid hex integer `{}`", s);
+        }
+        Inner::from_str_radix(digits, 16)
+            .map(U256)
+            .map_err(|_| anyhow::anyhow!("invalid hex integer `{}`", s))
+    }
+
+    /// Creates an integer from at most 32 big-endian bytes.
+    pub fn from_big_endian(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > 32 {
+            bail!(
+                "a 256-bit integer has at most 32 bytes, found {}",
+                bytes.len()
+            );
+        }
+        Ok(U256(Inner::from_big_endian(bytes)))
+    }
+
+    /// Returns the 32 big-endian bytes of the integer.
+    pub fn to_big_endian(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        self.0.to_big_endian(&mut bytes);
+        bytes
+    }
+
+    /// Parses a decimal amount in a unit with `decimals`, e.g. `"1.5"` with 18
+    /// decimals is `1500000000000000000`.
+    ///
+    /// It fails if the amount has more fractional digits than `decimals`, or
+    /// `decimals` is more than [`MAX_DECIMALS`](Self::MAX_DECIMALS).
+    pub fn parse_units(amount: &str, decimals: u32) -> Result<Self> {
+        if decimals > Self::MAX_DECIMALS {
+            bail!(
+                "the decimals {} are more than {}",
+                decimals,
+                Self::MAX_DECIMALS
+            );
+        }
+        let (int, frac) = amount.split_once('.').unwrap_or((amount, ""));
+        let all_digits = |s: &str| s.bytes().all(|c| c.is_ascii_digit());
+        if int.is_empty() && frac.is_empty() || !all_digits(int) || !all_digits(frac) {
+            bail!("invalid amount `{}`", amount);
+        }
+        let frac = frac.trim_end_matches('0');
+        if frac.len() > decimals as usize {
+            bail!(
+                "the amount `{}` has more than {} decimals",
+                amount,
+                decimals
+            );
+        }
+        let digits = format!(
+            "{}{}{}",
+            int,
+            frac,
+            "0".repeat(decimals as usize - frac.len())
+        );
+        let digits = digits.trim_start_matches('0');
+        if digits.is_empty() {
+            return Ok(U256::ZERO);
+        }
+        Self::from_dec_str(digits).with_context(|| format!("the amount `{}` overflows", amount))
+    }
+
+    /// Formats the integer as a decimal amount in a unit with `decimals`,
+    /// without the trailing zeros, e.g. `1500000000000000000` with 18 decimals
+    /// is `"1.5"`.
+    pub fn format_units(&self, decimals: u32) -> String {
+        let digits = self.0.to_string();
+        let decimals = decimals as usize;
+        let (int, frac) = if digits.len() > decimals {
+            digits.split_at(digits.len() - decimals)
+        } else {
+            ("0", digits.as_str())
+        };
+        let frac = format!("{:0>width$}", frac, width = decimals);
+        let frac = frac.trim_end_matches('0');
+        if frac.is_empty() {
+            int.to_string()
+        } else {
+            format!("{}.{}", int, frac)
+        }
+    }
+
+    /// Parses an amount of gwei into wei.
+    pub fn from_gwei(amount: &str) -> Result<Self> {
+        Self::parse_units(amount, Unit::Gwei.decimals())
+    }
+
+    /// Parses an amount of ether into wei.
+    pub fn from_ether(amount: &str) -> Result<Self> {
+        Self::parse_units(amount, Unit::Ether.decimals())
+    }
+
+    /// Formats the amount in wei as gwei.
+    pub fn to_gwei(&self) -> String {
+        self.format_units(Unit::Gwei.decimals())
+    }
+
+    /// Formats the amount in wei as ether.
+    pub fn to_ether(&self) -> String {
+        self.format_units(Unit::Ether.decimals())
+    }
+
+    /// Returns `10^n`, `None` if `n` is more than
+    /// [`MAX_DECIMALS`](Self::MAX_DECIMALS).
+    pub fn exp10(n: usize) -> Option<Self> {
+        (n <= Self::MAX_DECIMALS as usize).then(|| U256(Inner::exp10(n)))
+    }
+
+    /// Returns `true` if the integer is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Returns the bit at `index`, 0 for the least significant one.
+    pub fn bit(&self, index: usize) -> bool {
+        self.0.bit(index)
+    }
+
+    /// Returns the lowest 128 bits.
+    pub fn low_u128(&self) -> u128 {
+        self.0.low_u128()
+    }
+
+    /// Returns the integer as `u64` if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.0).ok()
+    }
+
+    /// Returns the integer as `u128` if it fits.
+    pub fn to_u128(&self) -> Option<u128> {
+        u128::try_from(self.0).ok()
+    }
+
+    /// Adds, returning `None` on overflow.
+    pub fn checked_add(self, other: U256) -> Option<U256> {
+        self.0.checked_add(other.0).map(U256)
+    }
+
+    /// Subtracts, returning `None` on underflow.
+    pub fn checked_sub(self, other: U256) -> Option<U256> {
+        self.0.checked_sub(other.0).map(U256)
+    }
+
+    /// Multiplies, returning `None` on overflow.
+    pub fn checked_mul(self, other: U256) -> Option<U256> {
+        self.0.checked_mul(other.0).map(U256)
+    }
+
+    /// Divides, returning `None` if `other` is zero.
+    pub fn checked_div(self, other: U256) -> Option<U256> {
+        self.0.checked_div(other.0).map(U256)
+    }
+
+    /// Adds, saturating at [`U256::MAX`].
+    pub fn saturating_add(self, other: U256) -> U256 {
+        U256(self.0.saturating_add(other.0))
+    }
+
+    /// Subtracts, saturating at zero.
+    pub fn saturating_sub(self, other: U256) -> U256 {
+        U256(self.0.saturating_sub(other.0))
+    }
+
+    /// Adds with wrapping, returning whether it overflows.
+    pub fn overflowing_add(self, other: U256) -> (U256, bool) {
+        let (v, overflow) = self.0.overflowing_add(other.0);
+        (U256(v), overflow)
+    }
+
+    /// Subtracts with wrapping, returning whether it underflows.
+    pub fn overflowing_sub(self, other: U256) -> (U256, bool) {
+        let (v, overflow) = self.0.overflowing_sub(other.0);
+        (U256(v), overflow)
+    }
+}
+
+macro_rules! impl_from_for_u256 {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for U256 {
+                fn from(v: $t) -> Self {
+                    U256(Inner::from(v))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_u256!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_try_from_for_u256 {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<$t> for U256 {
+                type Error = anyhow::Error;
+
+                fn try_from(v: $t) -> Result<Self> {
+                    if v < 0 {
+                        bail!("the negative value {} can't be a U256", v);
+                    }
+                    Ok(U256(Inner::from(v)))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_for_u256!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_op_for_u256 {
+    ($($trait:ident::$method:ident),*) => {
+        $(
+            impl $trait for U256 {
+                type Output = U256;
+
+                fn $method(self, other: U256) -> U256 {
+                    U256($trait::$method(self.0, other.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_op_for_u256!(Add::add, Sub::sub, Mul::mul, Div::div, Rem::rem);
+
+impl AddAssign for U256 {
+    fn add_assign(&mut self, other: U256) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for U256 {
+    fn sub_assign(&mut self, other: U256) {
+        *self = *self - other;
+    }
+}
+
+impl std::ops::Not for U256 {
+    type Output = U256;
+
+    fn not(self) -> U256 {
+        U256(!self.0)
+    }
+}
+
+impl FromStr for U256 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.starts_with("0x") {
+            return Self::from_hex_str(s);
+        }
+        match s.split_once(' ') {
+            Some((amount, unit)) => {
+                let unit = match unit.trim().to_ascii_lowercase().as_str() {
+                    "wei" => Unit::Wei,
+                    "gwei" => Unit::Gwei,
+                    "ether" | "eth" => Unit::Ether,
+                    _ => bail!("unknown unit `{}`", unit),
+                };
+                Self::parse_units(amount, unit.decimals())
+            }
+            None => Self::from_dec_str(s),
+        }
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl Serialize for U256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Repr {
+    Str(String),
+    Num(u64),
+}
+
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match Repr::deserialize(deserializer)? {
+            Repr::Str(s) => s.parse().map_err(serde::de::Error::custom),
+            Repr::Num(v) => Ok(U256::from(v)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units() {
+        let wei = U256::from_ether("1.25").unwrap();
+        assert_eq!(wei, U256::from(1_250_000_000_000_000_000u64));
+        assert_eq!(wei.to_ether(), "1.25");
+        assert_eq!(wei.to_gwei(), "1250000000");
+        assert_eq!(U256::from(1u64).to_gwei(), "0.000000001");
+        assert_eq!(U256::ZERO.to_ether(), "0");
+        assert_eq!(U256::from_gwei(".5").unwrap(), U256::from(500_000_000u64));
+        assert_eq!(
+            "20 gwei".parse::<U256>().unwrap(),
+            U256::from_gwei("20").unwrap()
+        );
+        assert_eq!("0xff".parse::<U256>().unwrap(), U256::from(255u64));
+        assert_eq!("255".parse::<U256>().unwrap(), U256::from(255u64));
+        assert!(U256::from_gwei("0.0000000001").is_err());
+        assert!(U256::from_ether("1.2.3").is_err());
+        assert!("12 btc".parse::<U256>().is_err());
+        assert!("ff".parse::<U256>().is_err());
+        assert_eq!(
+            U256::MAX.to_string(),
+            U256::MAX.to_string().parse::<U256>().unwrap().to_string()
+        );
+        assert!(U256::from_ether(&U256::MAX.to_string()).is_err());
+        assert_eq!(
+            U256::parse_units("1", 77).unwrap(),
+            U256::exp10(77).unwrap()
+        );
+        assert!(U256::parse_units("1", 78).is_err());
+        assert!(U256::parse_units("1", 4_000_000_000).is_err());
+        assert!(U256::exp10(78).is_none());
+
+        let v: U256 = serde_json::from_str(r#""1000""#).unwrap();
+        assert_eq!(serde_json::to_string(&v).unwrap(), r#""1000""#);
+        assert_eq!(serde_json::from_str::<U256>("7").unwrap(), U256::from(7u64));
+    }
+
+    #[test]
+    fn test_try_from_signed() {
+        assert_eq!(U256::try_from(42i32).unwrap(), U256::from(42u64));
+        assert_eq!(
+            U256::try_from(i128::MAX).unwrap(),
+            U256::from(i128::MAX as u128)
+        );
+        assert_eq!(U256::try_from(0isize).unwrap(), U256::ZERO);
+        assert!(U256::try_from(-1i64).is_err());
+        assert!(U256::try_from(i8::MIN).is_err());
+    }
+}
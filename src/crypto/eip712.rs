@@ -0,0 +1,930 @@
+//! Hash and sign the typed structured data by [EIP-712].
+//!
+//! A message is hashed with the domain of the contract verifying it, into
+//! the digest `keccak256(0x1901 ‖ domainSeparator ‖ hashStruct(message))`,
+//! which is signed with a secp256k1 private key. The contract recovers the
+//! signer from the 65-byte signature `r ‖ s ‖ v`.
+//!
+//! The message is either declared by a struct deriving [`Eip712`]:
+//!
+//! ```ignore
+//! use ws_sdk::crypto::eip712::{self, Domain, Eip712};
+//! use ws_sdk::blockchain::types::{Address, U256};
+//!
+//! #[derive(Eip712)]
+//! struct Attestation {
+//!     device: Address,
+//!     reading: U256,
+//!     timestamp: u64,
+//! }
+//!
+//! let domain = Domain::new("Attestor", "1", 4690, "0x3908c0620ABC34a23A078097e2e878AFf60bbC28".parse()?);
+//! let digest = attestation.digest(&domain);
+//! let sig = eip712::sign(prikey_hex, &digest)?;
+//! ```
+//!
+//! or given by the typed data in JSON, as the one of `eth_signTypedData_v4`,
+//! see [`TypedData`].
+//!
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+use super::secp256k1;
+#[cfg(feature = "abi")]
+use crate::blockchain::abi::{encode_call, Token};
+use crate::blockchain::types::{keccak256, Address, H256, U256};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Derives [`Eip712`] for a struct with named fields.
+///
+/// The name of the struct type is the name of the struct, unless it's given
+/// by `#[eip712(name = "...")]`, and a member is renamed by
+/// `#[eip712(rename = "...")]` on its field. The fields are the members in
+/// order, whose types implement [`Eip712Type`], including the other structs
+/// deriving [`Eip712`].
+///
+/// It's enabled by the `derive` feature flag.
+#[cfg(feature = "derive")]
+pub use ws_sdk_macros::Eip712;
+
+/// The domain of the signature, i.e. the `EIP712Domain` struct, where only
+/// the given fields are hashed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Domain {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifying_contract: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<H256>,
+}
+
+impl Domain {
+    /// Creates the usual domain of a contract with its name, version, chain
+    /// and address.
+    pub fn new(name: &str, version: &str, chain_id: u32, verifying_contract: Address) -> Self {
+        Domain {
+            name: Some(name.to_string()),
+            version: Some(version.to_string()),
+            chain_id: Some(U256::from(chain_id)),
+            verifying_contract: Some(verifying_contract),
+            salt: None,
+        }
+    }
+
+    /// Returns the domain separator, i.e. the struct hash of the domain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ws_sdk::crypto::eip712::Domain;
+    /// let domain = Domain::new(
+    ///     "Ether Mail",
+    ///     "1",
+    ///     1,
+    ///     "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC".parse()?,
+    /// );
+    /// assert_eq!(
+    ///     domain.separator().to_string(),
+    ///     "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    /// );
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn separator(&self) -> H256 {
+        let mut members = Vec::new();
+        let mut data = Vec::new();
+        if let Some(name) = &self.name {
+            members.push("string name");
+            data.extend(name.encode_value());
+        }
+        if let Some(version) = &self.version {
+            members.push("string version");
+            data.extend(version.encode_value());
+        }
+        if let Some(chain_id) = &self.chain_id {
+            members.push("uint256 chainId");
+            data.extend(chain_id.encode_value());
+        }
+        if let Some(contract) = &self.verifying_contract {
+            members.push("address verifyingContract");
+            data.extend(contract.encode_value());
+        }
+        if let Some(salt) = &self.salt {
+            members.push("bytes32 salt");
+            data.extend(salt.encode_value());
+        }
+        let encoded_type = format!("EIP712Domain({})", members.join(","));
+        hash_struct(&encoded_type, &data)
+    }
+}
+
+/// A type of the members of the structs, which is encoded into a word by
+/// `encodeData`.
+///
+/// It's implemented for the Rust types of the EIP-712 types:
+///
+/// | EIP-712                 | Rust                              |
+/// |-------------------------|-----------------------------------|
+/// | `address`               | [`Address`]                       |
+/// | `uint8` ... `uint128`   | `u8` ... `u128`                   |
+/// | `int8` ... `int128`     | `i8` ... `i128`                   |
+/// | `uint256`               | [`U256`]                          |
+/// | `bool`                  | `bool`                            |
+/// | `string`                | `String`                          |
+/// | `bytes`                 | `Vec<u8>`                         |
+/// | `bytes1` ... `bytes32`  | `[u8; 1]` ... `[u8; 32]`, or [`H256`] |
+/// | `T[]`                   | `Vec<T>`                          |
+/// | `T[k]`                  | `[T; k]`                          |
+/// | a struct                | a struct deriving [`Eip712`]      |
+///
+/// A longer array of bytes has no EIP-712 type, e.g. a 65-byte signature is
+/// a `Vec<u8>`:
+///
+/// ```compile_fail
+/// use ws_sdk::crypto::eip712::Eip712Type;
+/// let name = <[u8; 65] as Eip712Type>::type_name();
+/// ```
+pub trait Eip712Type {
+    /// Returns the name of the type in `encodeType`, e.g. `uint256`.
+    fn type_name() -> String;
+
+    /// Collects the encoded types of the structs referenced by the type, by
+    /// their names.
+    fn collect_structs(_structs: &mut BTreeMap<String, String>) {}
+
+    /// Encodes the value into a word, where the dynamic values and the
+    /// structs are hashed.
+    fn encode_value(&self) -> [u8; 32];
+}
+
+/// An [`Eip712Type`] which can be an element of an array.
+///
+/// It's implemented for all the types except `u8`, whose arrays are encoded
+/// as bytes.
+pub trait Eip712Item: Eip712Type {}
+
+/// A struct of the typed structured data, usually derived by
+/// [`derive@Eip712`].
+pub trait Eip712: Eip712Type {
+    /// Encodes the members in order by `encodeData`, without the type hash.
+    fn encode_data(&self) -> Vec<u8>;
+
+    /// Returns `encodeType` of the struct, e.g.
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    fn encode_type() -> String {
+        let mut structs = BTreeMap::new();
+        Self::collect_structs(&mut structs);
+        let primary = structs.remove(&Self::type_name()).unwrap_or_default();
+        structs.into_values().fold(primary, |s, t| s + &t)
+    }
+
+    /// Returns the hash of `encodeType`.
+    fn type_hash() -> H256 {
+        H256::from(keccak256(Self::encode_type().as_bytes()))
+    }
+
+    /// Returns `hashStruct` of the value.
+    fn struct_hash(&self) -> H256 {
+        hash_struct(&Self::encode_type(), &self.encode_data())
+    }
+
+    /// Returns the digest to be signed in the domain.
+    fn digest(&self, domain: &Domain) -> H256 {
+        digest(&domain.separator(), &self.struct_hash())
+    }
+}
+
+/// Returns the digest to be signed of a struct hash in a domain, i.e.
+/// `keccak256(0x1901 ‖ domainSeparator ‖ structHash)`.
+pub fn digest(domain_separator: &H256, struct_hash: &H256) -> H256 {
+    let mut data = Vec::with_capacity(66);
+    data.extend_from_slice(&[0x19, 0x01]);
+    data.extend_from_slice(domain_separator.as_bytes());
+    data.extend_from_slice(struct_hash.as_bytes());
+    H256::from(keccak256(&data))
+}
+
+fn hash_struct(encoded_type: &str, data: &[u8]) -> H256 {
+    let mut encoded = keccak256(encoded_type.as_bytes()).to_vec();
+    encoded.extend_from_slice(data);
+    H256::from(keccak256(&encoded))
+}
+
+/// Signs the digest with the private key in hex.
+///
+/// The signature is the 65 bytes `r ‖ s ‖ v` in hex, where `v` is 27 or 28,
+/// as verified by `ecrecover` in the contracts. See
+/// [`sign_recoverable`](super::secp256k1::sign_recoverable) for the signature
+/// in its fields.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::crypto::eip712::{recover, sign};
+/// use ws_sdk::blockchain::types::H256;
+/// let digest = H256::from([0x11; 32]);
+/// let sig = sign("4646464646464646464646464646464646464646464646464646464646464646", &digest)?;
+/// assert_eq!(
+///     recover(&digest, &sig)?.to_string(),
+///     "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+/// );
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn sign(prikey_hex: &str, digest: &H256) -> Result<String> {
+    Ok(secp256k1::sign_recoverable(prikey_hex, digest)?.to_string())
+}
+
+/// Recovers the address of the signer of the digest from the signature in
+/// hex, whose `v` is either 27 or 28, or the recovery id 0 or 1.
+pub fn recover(digest: &H256, sig_hex: &str) -> Result<Address> {
+    Ok(secp256k1::recover(digest, &sig_hex.parse()?)?.address())
+}
+
+/// Verifies that the signature in hex of the digest is signed by `signer`.
+pub fn verify(digest: &H256, sig_hex: &str, signer: Address) -> Result<()> {
+    let recovered = recover(digest, sig_hex)?;
+    if recovered != signer {
+        bail!("the signature is signed by {}, not {}", recovered, signer);
+    }
+    Ok(())
+}
+
+/// A member of a struct in the typed data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// The typed structured data in JSON, as the one of `eth_signTypedData_v4`.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::crypto::eip712::TypedData;
+/// let data = TypedData::from_json(r#"{
+///     "types": {
+///         "EIP712Domain": [
+///             {"name": "name", "type": "string"},
+///             {"name": "chainId", "type": "uint256"}
+///         ],
+///         "Reading": [
+///             {"name": "device", "type": "address"},
+///             {"name": "value", "type": "int64"}
+///         ]
+///     },
+///     "primaryType": "Reading",
+///     "domain": {"name": "Attestor", "chainId": 4690},
+///     "message": {"device": "0x3908c0620ABC34a23A078097e2e878AFf60bbC28", "value": -7}
+/// }"#)?;
+/// assert_eq!(data.encode_type()?, "Reading(address device,int64 value)");
+/// let digest = data.digest()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    pub domain: Domain,
+    pub message: Value,
+}
+
+impl TypedData {
+    /// Parses the typed data in JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("invalid typed data")
+    }
+
+    /// Returns `encodeType` of the primary type.
+    pub fn encode_type(&self) -> Result<String> {
+        self.encode_struct_type(&self.primary_type)
+    }
+
+    /// Returns `hashStruct` of the message.
+    pub fn struct_hash(&self) -> Result<H256> {
+        let data = self.encode_data(&self.primary_type, &self.message)?;
+        Ok(hash_struct(&self.encode_type()?, &data))
+    }
+
+    /// Returns the digest to be signed.
+    pub fn digest(&self) -> Result<H256> {
+        Ok(digest(&self.domain.separator(), &self.struct_hash()?))
+    }
+
+    fn fields(&self, name: &str) -> Result<&[TypedField]> {
+        self.types
+            .get(name)
+            .map(|fields| &fields[..])
+            .with_context(|| format!("unknown struct type `{}`", name))
+    }
+
+    fn encode_struct_type(&self, name: &str) -> Result<String> {
+        let mut deps = BTreeSet::new();
+        self.collect_deps(name, &mut deps)?;
+        deps.remove(name);
+        let mut encoded = String::new();
+        for name in std::iter::once(name).chain(deps.iter().copied()) {
+            let members: Vec<String> = self
+                .fields(name)?
+                .iter()
+                .map(|f| format!("{} {}", f.kind, f.name))
+                .collect();
+            encoded += &format!("{}({})", name, members.join(","));
+        }
+        Ok(encoded)
+    }
+
+    fn collect_deps<'a>(&'a self, name: &'a str, deps: &mut BTreeSet<&'a str>) -> Result<()> {
+        if !deps.insert(name) {
+            return Ok(());
+        }
+        for field in self.fields(name)? {
+            let base = field.kind.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base) {
+                self.collect_deps(base, deps)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_data(&self, name: &str, value: &Value) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        for field in self.fields(name)? {
+            let member = value.get(&field.name).unwrap_or(&Value::Null);
+            let word = self
+                .encode_value(&field.kind, member)
+                .with_context(|| format!("invalid member `{}` of `{}`", field.name, name))?;
+            data.extend(word);
+        }
+        Ok(data)
+    }
+
+    fn encode_value(&self, kind: &str, value: &Value) -> Result<[u8; 32]> {
+        if let Some((item, len)) = split_array(kind)? {
+            let items = value.as_array().context("expected an array")?;
+            if matches!(len, Some(len) if len != items.len()) {
+                bail!("expected {} items, found {}", len.unwrap(), items.len());
+            }
+            let mut data = Vec::with_capacity(items.len() * 32);
+            for v in items {
+                data.extend(self.encode_value(item, v)?);
+            }
+            return Ok(keccak256(&data));
+        }
+        if self.types.contains_key(kind) {
+            if !value.is_object() {
+                bail!("expected a struct `{}`", kind);
+            }
+            let data = self.encode_data(kind, value)?;
+            return Ok(*hash_struct(&self.encode_struct_type(kind)?, &data).as_bytes());
+        }
+        match atomic(kind, value)? {
+            Atomic::Word(word) => Ok(word),
+            Atomic::Bytes(bytes) => Ok(keccak256(&bytes)),
+        }
+    }
+
+    /// Encodes the calldata of the function `function` of the contract
+    /// verifying the message, with the message as a tuple and the signature
+    /// as `bytes`, e.g. `attest((address,int64),bytes)` for the example of
+    /// [`TypedData`].
+    ///
+    /// It's enabled by the `abi` feature flag.
+    #[cfg(feature = "abi")]
+    pub fn calldata(&self, function: &str, sig_hex: &str) -> Result<Vec<u8>> {
+        let signature = hex::decode(sig_hex.strip_prefix("0x").unwrap_or(sig_hex))
+            .context("invalid signature")?;
+        let message = self.abi_type(&self.primary_type)?;
+        encode_call(
+            function,
+            &[&message, "bytes"],
+            vec![
+                self.token(&self.primary_type, &self.message)?,
+                Token::Bytes(signature),
+            ],
+        )
+    }
+
+    // The canonical ABI type, where a struct is a tuple.
+    #[cfg(feature = "abi")]
+    fn abi_type(&self, kind: &str) -> Result<String> {
+        if let Some((item, _)) = split_array(kind)? {
+            return Ok(format!("{}{}", self.abi_type(item)?, &kind[item.len()..]));
+        }
+        if !self.types.contains_key(kind) {
+            return Ok(kind.to_string());
+        }
+        let members = self
+            .fields(kind)?
+            .iter()
+            .map(|f| self.abi_type(&f.kind))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(format!("({})", members.join(",")))
+    }
+
+    #[cfg(feature = "abi")]
+    fn token(&self, kind: &str, value: &Value) -> Result<Token> {
+        if let Some((item, len)) = split_array(kind)? {
+            let items = value
+                .as_array()
+                .context("expected an array")?
+                .iter()
+                .map(|v| self.token(item, v))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(match len {
+                Some(len) if len != items.len() => {
+                    bail!("expected {} items, found {}", len, items.len())
+                }
+                Some(_) => Token::FixedArray(items),
+                None => Token::Array(items),
+            });
+        }
+        if self.types.contains_key(kind) {
+            let members = self
+                .fields(kind)?
+                .iter()
+                .map(|f| {
+                    let member = value.get(&f.name).unwrap_or(&Value::Null);
+                    self.token(&f.kind, member)
+                        .with_context(|| format!("invalid member `{}` of `{}`", f.name, kind))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Token::Tuple(members));
+        }
+        let uint = |word: [u8; 32]| ethabi::Uint::from_big_endian(&word);
+        Ok(match (kind, atomic(kind, value)?) {
+            ("address", Atomic::Word(word)) => {
+                Token::Address(ethabi::Address::from_slice(&word[12..]))
+            }
+            ("bool", Atomic::Word(word)) => Token::Bool(word[31] == 1),
+            ("string", Atomic::Bytes(bytes)) => Token::String(String::from_utf8(bytes)?),
+            ("bytes", Atomic::Bytes(bytes)) => Token::Bytes(bytes),
+            (kind, Atomic::Word(word)) if kind.starts_with("bytes") => {
+                let len: usize = kind["bytes".len()..].parse()?;
+                Token::FixedBytes(word[..len].to_vec())
+            }
+            (kind, Atomic::Word(word)) if kind.starts_with("int") => Token::Int(uint(word)),
+            (_, Atomic::Word(word)) => Token::Uint(uint(word)),
+            (_, Atomic::Bytes(_)) => unreachable!(),
+        })
+    }
+}
+
+// Splits an array type into the type of its items and its length, if it's
+// fixed.
+fn split_array(kind: &str) -> Result<Option<(&str, Option<usize>)>> {
+    let Some(inner) = kind.strip_suffix(']') else {
+        return Ok(None);
+    };
+    let (item, len) = inner
+        .rsplit_once('[')
+        .with_context(|| format!("invalid type `{}`", kind))?;
+    let len = match len {
+        "" => None,
+        len => Some(
+            len.parse()
+                .with_context(|| format!("invalid type `{}`", kind))?,
+        ),
+    };
+    Ok(Some((item, len)))
+}
+
+// An atomic value, which is a word, or the dynamic bytes to be hashed.
+enum Atomic {
+    Word([u8; 32]),
+    Bytes(Vec<u8>),
+}
+
+fn atomic(kind: &str, value: &Value) -> Result<Atomic> {
+    let hex_bytes = |value: &Value| -> Result<Vec<u8>> {
+        let s = value.as_str().context("expected the bytes in hex")?;
+        hex::decode(s.strip_prefix("0x").unwrap_or(s)).context("invalid hex")
+    };
+    let word = match kind {
+        "string" => {
+            let s = value.as_str().context("expected a string")?;
+            return Ok(Atomic::Bytes(s.as_bytes().to_vec()));
+        }
+        "bytes" => return Ok(Atomic::Bytes(hex_bytes(value)?)),
+        "bool" => value.as_bool().context("expected a bool")?.encode_value(),
+        "address" => value
+            .as_str()
+            .context("expected an address")?
+            .parse::<Address>()?
+            .encode_value(),
+        _ => {
+            if let Some(len) = kind.strip_prefix("bytes") {
+                let len: usize = len
+                    .parse()
+                    .ok()
+                    .filter(|n| (1..=32).contains(n))
+                    .with_context(|| format!("invalid type `{}`", kind))?;
+                let bytes = hex_bytes(value)?;
+                if bytes.len() > len {
+                    bail!("expected at most {} bytes, found {}", len, bytes.len());
+                }
+                let mut word = [0; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                word
+            } else if let Some(bits) = kind.strip_prefix("uint") {
+                let (v, negative) = integer(bits, kind, value)?;
+                if negative {
+                    bail!("expected an unsigned integer");
+                }
+                v.to_big_endian()
+            } else if let Some(bits) = kind.strip_prefix("int") {
+                let (v, negative) = integer(bits, kind, value)?;
+                let v = if negative { !(v - U256::ONE) } else { v };
+                v.to_big_endian()
+            } else {
+                bail!("unknown type `{}`", kind)
+            }
+        }
+    };
+    Ok(Atomic::Word(word))
+}
+
+// Parses an integer of `bits` from a number, or a decimal or "0x" hex
+// string, into its absolute value and its sign.
+fn integer(bits: &str, kind: &str, value: &Value) -> Result<(U256, bool)> {
+    let bits: usize = match bits {
+        "" => 256,
+        bits => bits
+            .parse()
+            .ok()
+            .filter(|n| n % 8 == 0 && (8..=256).contains(n))
+            .with_context(|| format!("invalid type `{}`", kind))?,
+    };
+    let (abs, negative) = match value {
+        Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(n), _) => (U256::from(n), false),
+            (_, Some(n)) => (U256::from(n.unsigned_abs()), true),
+            _ => bail!("expected an integer, found {}", n),
+        },
+        Value::String(s) => {
+            let s = s.trim();
+            let (s, negative) = match s.strip_prefix('-') {
+                Some(s) => (s, true),
+                None => (s, false),
+            };
+            let abs = if s.starts_with("0x") {
+                U256::from_hex_str(s)?
+            } else {
+                U256::from_dec_str(s)?
+            };
+            (abs, negative)
+        }
+        _ => bail!("expected an integer"),
+    };
+    let limit = if kind.starts_with("int") {
+        bits - 1
+    } else {
+        bits
+    };
+    // 2^limit, the bound of the negative values
+    let bound = (0..limit.min(255)).fold(U256::ONE, |v, _| v + v);
+    if limit < 256 && abs > bound - U256::from(!negative as u8) {
+        bail!("{} overflows {}", value, kind);
+    }
+    Ok((abs, negative && !abs.is_zero()))
+}
+
+impl Eip712Type for Address {
+    fn type_name() -> String {
+        "address".to_string()
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        let mut word = [0; 32];
+        word[12..].copy_from_slice(self.as_bytes());
+        word
+    }
+}
+
+impl Eip712Type for U256 {
+    fn type_name() -> String {
+        "uint256".to_string()
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        self.to_big_endian()
+    }
+}
+
+impl Eip712Type for H256 {
+    fn type_name() -> String {
+        "bytes32".to_string()
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        *self.as_bytes()
+    }
+}
+
+impl Eip712Type for bool {
+    fn type_name() -> String {
+        "bool".to_string()
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        U256::from(*self as u8).to_big_endian()
+    }
+}
+
+impl Eip712Type for String {
+    fn type_name() -> String {
+        "string".to_string()
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        keccak256(self.as_bytes())
+    }
+}
+
+impl Eip712Type for Vec<u8> {
+    fn type_name() -> String {
+        "bytes".to_string()
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        keccak256(self)
+    }
+}
+
+// `bytes1` to `bytes32`, so that no other array of bytes is a member.
+macro_rules! impl_eip712_type_for_bytes {
+    ($($n:literal),*) => {
+        $(
+            impl Eip712Type for [u8; $n] {
+                fn type_name() -> String {
+                    String::from(concat!("bytes", $n))
+                }
+
+                fn encode_value(&self) -> [u8; 32] {
+                    let mut word = [0; 32];
+                    word[..$n].copy_from_slice(self);
+                    word
+                }
+            }
+
+            impl Eip712Item for [u8; $n] {}
+        )*
+    };
+}
+
+impl_eip712_type_for_bytes!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32
+);
+
+impl<T: Eip712Item> Eip712Type for Vec<T> {
+    fn type_name() -> String {
+        format!("{}[]", T::type_name())
+    }
+
+    fn collect_structs(structs: &mut BTreeMap<String, String>) {
+        T::collect_structs(structs)
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        let data: Vec<u8> = self.iter().flat_map(|v| v.encode_value()).collect();
+        keccak256(&data)
+    }
+}
+
+impl<T: Eip712Item, const N: usize> Eip712Type for [T; N] {
+    fn type_name() -> String {
+        format!("{}[{}]", T::type_name(), N)
+    }
+
+    fn collect_structs(structs: &mut BTreeMap<String, String>) {
+        T::collect_structs(structs)
+    }
+
+    fn encode_value(&self) -> [u8; 32] {
+        let data: Vec<u8> = self.iter().flat_map(|v| v.encode_value()).collect();
+        keccak256(&data)
+    }
+}
+
+macro_rules! impl_eip712_type_for_int {
+    ($($t:ty => $name:literal),*) => {
+        $(
+            impl Eip712Type for $t {
+                fn type_name() -> String {
+                    $name.to_string()
+                }
+
+                fn encode_value(&self) -> [u8; 32] {
+                    // sign extended to 256 bits
+                    #[allow(unused_comparisons)]
+                    let mut word = if *self < 0 { [0xff; 32] } else { [0; 32] };
+                    let bytes = self.to_be_bytes();
+                    word[32 - bytes.len()..].copy_from_slice(&bytes);
+                    word
+                }
+            }
+        )*
+    };
+}
+
+impl_eip712_type_for_int!(
+    u8 => "uint8", u16 => "uint16", u32 => "uint32", u64 => "uint64", u128 => "uint128",
+    i8 => "int8", i16 => "int16", i32 => "int32", i64 => "int64", i128 => "int128"
+);
+
+macro_rules! impl_eip712_item {
+    ($($t:ty),*) => {
+        $(impl Eip712Item for $t {})*
+    };
+}
+
+impl_eip712_item!(
+    Address,
+    U256,
+    H256,
+    bool,
+    String,
+    Vec<u8>,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128
+);
+impl<T: Eip712Item> Eip712Item for Vec<T> {}
+impl<T: Eip712Item, const N: usize> Eip712Item for [T; N] {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the example of EIP-712
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallet", "type": "address"}
+            ],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person"},
+                {"name": "contents", "type": "string"}
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+            "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    struct Person {
+        name: String,
+        wallet: Address,
+    }
+
+    impl Eip712Type for Person {
+        fn type_name() -> String {
+            "Person".to_string()
+        }
+
+        fn collect_structs(structs: &mut BTreeMap<String, String>) {
+            structs.insert(
+                "Person".to_string(),
+                "Person(string name,address wallet)".to_string(),
+            );
+        }
+
+        fn encode_value(&self) -> [u8; 32] {
+            *self.struct_hash().as_bytes()
+        }
+    }
+
+    impl Eip712 for Person {
+        fn encode_data(&self) -> Vec<u8> {
+            [self.name.encode_value(), self.wallet.encode_value()].concat()
+        }
+    }
+
+    #[test]
+    fn test_typed_data() {
+        let data = TypedData::from_json(MAIL).unwrap();
+        assert_eq!(
+            data.encode_type().unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            data.struct_hash().unwrap().to_string(),
+            "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        let digest = data.digest().unwrap();
+        assert_eq!(
+            digest.to_string(),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+
+        // the private key of keccak256("cow")
+        let key = hex::encode(keccak256(b"cow"));
+        let sig = sign(&key, &digest).unwrap();
+        assert_eq!(
+            sig,
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c"
+        );
+        let signer = recover(&digest, &sig).unwrap();
+        assert_eq!(
+            signer.to_string(),
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+        );
+        assert!(verify(&digest, &sig, signer).is_ok());
+        assert!(verify(&digest, &sig, Address::ZERO).is_err());
+
+        let cow = Person {
+            name: "Cow".into(),
+            wallet: "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                .parse()
+                .unwrap(),
+        };
+        assert_eq!(
+            Person::type_hash(),
+            H256::from(keccak256(b"Person(string name,address wallet)"))
+        );
+        let mut types = BTreeMap::new();
+        types.insert("Person".to_string(), data.types["Person"].clone());
+        let person = TypedData {
+            types,
+            primary_type: "Person".into(),
+            domain: data.domain.clone(),
+            message: data.message["from"].clone(),
+        };
+        assert_eq!(cow.struct_hash(), person.struct_hash().unwrap());
+    }
+
+    #[test]
+    fn test_encode_values() {
+        assert_eq!(<Vec<[u8; 4]>>::type_name(), "bytes4[]");
+        assert_eq!(<[Vec<u8>; 2]>::type_name(), "bytes[2]");
+        assert_eq!((-1_i64).encode_value(), [0xff; 32]);
+        assert_eq!(300_u16.encode_value()[30..], [1, 44]);
+
+        let data = TypedData::from_json(MAIL).unwrap();
+        let encode = |kind: &str, value: Value| data.encode_value(kind, &value);
+        assert_eq!(
+            encode("int8", Value::from(-1)).unwrap(),
+            (-1_i8).encode_value()
+        );
+        assert_eq!(
+            encode("uint256", Value::from("0x12c")).unwrap(),
+            300_u16.encode_value()
+        );
+        assert_eq!(
+            encode("bytes4", Value::from("0x01020304")).unwrap(),
+            [1_u8, 2, 3, 4].encode_value()
+        );
+        assert_eq!(
+            encode("bytes", Value::from("0x0102")).unwrap(),
+            vec![1_u8, 2].encode_value()
+        );
+        assert_eq!(
+            encode("string[]", serde_json::json!(["a", "b"])).unwrap(),
+            vec![String::from("a"), String::from("b")].encode_value()
+        );
+        assert_eq!(
+            encode("int8", Value::from(-128)).unwrap(),
+            (-128_i8).encode_value()
+        );
+        assert!(encode("int8", Value::from(128)).is_err());
+        assert!(encode("uint8", Value::from(-1)).is_err());
+        assert!(encode("uint8", Value::from(256)).is_err());
+        assert!(encode("bytes2", Value::from("0x010203")).is_err());
+        assert!(encode("string[1]", serde_json::json!(["a", "b"])).is_err());
+        assert!(encode("Person", Value::from("Cow")).is_err());
+        assert!(encode("uint7", Value::from(1)).is_err());
+        assert!(encode("float", Value::from(1)).is_err());
+    }
+}
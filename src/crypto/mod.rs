@@ -15,5 +15,6 @@
 //! ```
 //!
 //!
+pub mod eip712;
 pub mod secp256k1;
 pub mod secp256r1;
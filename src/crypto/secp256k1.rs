@@ -1,10 +1,14 @@
+use crate::blockchain::types::{keccak256, Address, H256};
 use anyhow::{bail, Context, Ok, Result};
 use k256::{
     ecdsa::{
-        signature::Signer, signature::Verifier, DerSignature, Signature, SigningKey, VerifyingKey,
+        signature::Signer, signature::Verifier, DerSignature, RecoveryId, Signature, SigningKey,
+        VerifyingKey,
     },
     EncodedPoint,
 };
+use std::fmt;
+use std::str::FromStr;
 
 /// get the signature by signing the payload with the private key.
 ///
@@ -113,6 +117,224 @@ pub fn pubkey(prikey_hex: &str) -> Result<String> {
     Ok(hex::encode(pbk.to_bytes().to_vec()))
 }
 
+/// A recoverable signature `{v, r, s}` in Ethereum style, where `v` is 27 or
+/// 28 for the recovery id 0 or 1.
+///
+/// It's encoded in the 65 bytes `r ‖ s ‖ v`, as verified by `ecrecover` in the
+/// contracts, and parsed with `v` being either 27 or 28, or 0 or 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoverableSignature {
+    pub v: u8,
+    pub r: H256,
+    pub s: H256,
+}
+
+impl RecoverableSignature {
+    /// Parses the 65 bytes `r ‖ s ‖ v`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 65 {
+            bail!("invalid signature of {} bytes, expected 65", bytes.len());
+        }
+        let mut sig = RecoverableSignature {
+            v: bytes[64],
+            r: H256::from_slice(&bytes[..32])?,
+            s: H256::from_slice(&bytes[32..64])?,
+        };
+        sig.v = 27 + sig.recovery_id()?;
+        Ok(sig)
+    }
+
+    /// Returns the 65 bytes `r ‖ s ‖ v`.
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0; 65];
+        bytes[..32].copy_from_slice(self.r.as_bytes());
+        bytes[32..64].copy_from_slice(self.s.as_bytes());
+        bytes[64] = self.v;
+        bytes
+    }
+
+    /// Returns the recovery id, i.e. the parity of the y coordinate of `r`,
+    /// from `v` being either 27 or 28, or 0 or 1.
+    pub fn recovery_id(&self) -> Result<u8> {
+        match self.v {
+            v @ (0 | 1) => Ok(v),
+            v @ (27 | 28) => Ok(v - 27),
+            v => bail!("invalid v {} of the signature", v),
+        }
+    }
+
+    fn split(&self) -> Result<(Signature, RecoveryId)> {
+        let sig = Signature::from_scalars(*self.r.as_bytes(), *self.s.as_bytes())
+            .context("fail to decode the signature")?;
+        let recid = RecoveryId::from_byte(self.recovery_id()?).context("invalid recovery id")?;
+        Ok((sig, recid))
+    }
+}
+
+impl fmt::Display for RecoverableSignature {
+    /// Formats the signature in hex without the `0x` prefix, as the other
+    /// signatures of the module.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.to_bytes()))
+    }
+}
+
+impl FromStr for RecoverableSignature {
+    type Err = anyhow::Error;
+
+    /// Parses the signature in hex, with or without the `0x` prefix.
+    fn from_str(s: &str) -> Result<Self> {
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s)).context("invalid signature")?;
+        RecoverableSignature::from_bytes(&bytes)
+    }
+}
+
+/// A public key, e.g. recovered from a signature by [`recover`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(VerifyingKey);
+
+impl PublicKey {
+    /// Parses the public key in hex, either uncompressed or compressed.
+    pub fn from_hex(pubkey_hex: &str) -> Result<Self> {
+        let bytes = hex::decode(pubkey_hex.strip_prefix("0x").unwrap_or(pubkey_hex))
+            .context("invalid public key")?;
+        get_verifyingkey(&bytes).map(PublicKey)
+    }
+
+    /// Returns the uncompressed public key in hex, as [`pubkey`].
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0.to_encoded_point(false).as_bytes())
+    }
+
+    /// Returns the Ethereum address of the public key.
+    pub fn address(&self) -> Address {
+        address_of(&self.0)
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// sign the 32-byte hash of a message with the private key into a recoverable signature.
+///
+/// The hash is signed as is, so it should be computed by the caller, e.g. by `keccak256`;
+/// the private key is in hex form.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::blockchain::types::{keccak256, H256};
+/// use ws_sdk::crypto::secp256k1::{recover, sign_recoverable};
+/// let pvk_hex = "4582b2bf2611f8fe5f7d4e22e20ff19dda42ca630344b33831695c02b616c819";
+/// let hash = H256::from(keccak256(b"sample"));
+/// let sig = sign_recoverable(pvk_hex, &hash)?;
+/// assert_eq!(
+///     recover(&hash, &sig)?.to_hex(),
+///     "04437203fefbba6922efdfd3b60611f47bbfc7d1472c16506a4ec7f27cec5b3357ec17e87add178dbe6e6eaf3707b2e73c5fa94ed0fb59553ed8ed485e1e6ba3fb"
+/// );
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn sign_recoverable(prikey_hex: &str, msg_hash: &H256) -> Result<RecoverableSignature> {
+    sign_prehash(&get_signingkey(prikey_hex)?, msg_hash)
+}
+
+/// recover the public key of the signer from the 32-byte hash of a message and its signature.
+pub fn recover(msg_hash: &H256, sig: &RecoverableSignature) -> Result<PublicKey> {
+    let (sig, recid) = sig.split()?;
+    VerifyingKey::recover_from_prehash(msg_hash.as_bytes(), &sig, recid)
+        .map(PublicKey)
+        .context("fail to recover the public key")
+}
+
+/// get the Ethereum address of the public key, i.e. the last 20 bytes of the `keccak256` hash
+/// of the uncompressed public key without its `0x04` prefix.
+///
+/// The public key is in hex form, either uncompressed or compressed.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::crypto::secp256k1::{address_from_pubkey, pubkey};
+/// let pvk_hex = "4646464646464646464646464646464646464646464646464646464646464646";
+/// let address = address_from_pubkey(&pubkey(pvk_hex)?)?;
+/// assert_eq!(address.to_string(), "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn address_from_pubkey(pubkey_hex: &str) -> Result<Address> {
+    Ok(PublicKey::from_hex(pubkey_hex)?.address())
+}
+
+/// get the hash of a message signed by [EIP-191] `personal_sign`, i.e. the `keccak256` hash of
+/// `"\x19Ethereum Signed Message:\n" ‖ len(message) ‖ message`.
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+pub fn hash_personal_message(message: &[u8]) -> H256 {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    H256::from(keccak256(&data))
+}
+
+/// sign the message as [EIP-191] `personal_sign` of the wallets with the private key.
+///
+/// The private key is in hex form, and the message is in bytes array.
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::crypto::secp256k1::{personal_sign, verify_personal};
+/// let pvk_hex = "4646464646464646464646464646464646464646464646464646464646464646";
+/// let sig = personal_sign(pvk_hex, b"hello")?;
+/// let address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse()?;
+/// assert!(verify_personal(address, b"hello", &sig).is_ok());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn personal_sign(prikey_hex: &str, message: &[u8]) -> Result<RecoverableSignature> {
+    sign_recoverable(prikey_hex, &hash_personal_message(message))
+}
+
+/// verify the [EIP-191] `personal_sign` signature of the message is signed by the address.
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+pub fn verify_personal(address: Address, message: &[u8], sig: &RecoverableSignature) -> Result<()> {
+    let signer = recover(&hash_personal_message(message), sig)?.address();
+    if signer != address {
+        bail!("the message is signed by {}, not {}", signer, address);
+    }
+    Ok(())
+}
+
+pub(crate) fn address_of(key: &VerifyingKey) -> Address {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    Address::from(<[u8; 20]>::try_from(&hash[12..]).unwrap())
+}
+
+// Parses the private key in hex, with or without the `0x` prefix.
+pub(crate) fn get_signingkey(prikey_hex: &str) -> Result<SigningKey> {
+    let bytes = hex::decode(prikey_hex.strip_prefix("0x").unwrap_or(prikey_hex))
+        .context("invalid private key")?;
+    SigningKey::from_slice(&bytes).context("fail to get the private key")
+}
+
+// Signs the hash as is, shared by the recoverable signatures of the module
+// and the transactions of the wallets.
+pub(crate) fn sign_prehash(key: &SigningKey, hash: &H256) -> Result<RecoverableSignature> {
+    let (sig, recid) = key
+        .sign_prehash_recoverable(hash.as_bytes())
+        .context("fail to sign the hash")?;
+    let bytes = sig.to_bytes();
+    Ok(RecoverableSignature {
+        v: 27 + recid.to_byte(),
+        r: H256::from_slice(&bytes[..32])?,
+        s: H256::from_slice(&bytes[32..])?,
+    })
+}
+
 fn sign_the_message(prikey_hex: &str, data_bytes: &[u8]) -> Result<Signature> {
     let signer =
         SigningKey::from_slice(&hex::decode(prikey_hex)?).context("fail to get the private key")?;
@@ -135,6 +357,7 @@ fn get_verifyingkey(pubkey_bytes: &[u8]) -> Result<VerifyingKey> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use k256::ecdsa::signature::hazmat::PrehashVerifier;
 
     #[test]
     fn test_k256() {
@@ -150,4 +373,74 @@ mod tests {
         let sig = sign(pvk_hex, message.as_bytes()).unwrap();
         assert!(verify(pbk_hex, message.as_bytes(), &sig).is_ok())
     }
+
+    #[test]
+    fn test_recover() {
+        let pvk_hex = "4582b2bf2611f8fe5f7d4e22e20ff19dda42ca630344b33831695c02b616c819";
+        let pbk_hex: &str = "04437203fefbba6922efdfd3b60611f47bbfc7d1472c16506a4ec7f27cec5b3357ec17e87add178dbe6e6eaf3707b2e73c5fa94ed0fb59553ed8ed485e1e6ba3fb";
+        let hash = H256::from(keccak256(b"sample"));
+
+        let sig = sign_recoverable(pvk_hex, &hash).unwrap();
+        assert!(sig.v == 27 || sig.v == 28);
+        assert_eq!(recover(&hash, &sig).unwrap().to_hex(), pbk_hex);
+        assert_eq!(
+            sig.to_string().parse::<RecoverableSignature>().unwrap(),
+            sig
+        );
+        let mut bytes = sig.to_bytes();
+        bytes[64] -= 27;
+        assert_eq!(RecoverableSignature::from_bytes(&bytes).unwrap(), sig);
+        bytes[64] = 2;
+        assert!(RecoverableSignature::from_bytes(&bytes).is_err());
+        assert!(RecoverableSignature::from_bytes(&bytes[..64]).is_err());
+
+        // v of 0 or 1 set on the fields
+        let raw = RecoverableSignature {
+            v: sig.v - 27,
+            ..sig
+        };
+        assert_eq!(raw.recovery_id().unwrap(), sig.recovery_id().unwrap());
+        assert_eq!(recover(&hash, &raw).unwrap().to_hex(), pbk_hex);
+        let invalid = RecoverableSignature { v: 2, ..sig };
+        assert!(recover(&hash, &invalid).is_err());
+
+        // the non-recoverable signature of the same hash
+        let bytes = sig.to_bytes();
+        let plain = Signature::from_slice(&bytes[..64]).unwrap();
+        assert!(get_verifyingkey(&hex::decode(pbk_hex).unwrap())
+            .unwrap()
+            .verify_prehash(hash.as_bytes(), &plain)
+            .is_ok());
+
+        let other = H256::from(keccak256(b"other"));
+        assert_ne!(recover(&other, &sig).unwrap().to_hex(), pbk_hex);
+    }
+
+    #[test]
+    fn test_personal_sign() {
+        let pvk_hex = "4646464646464646464646464646464646464646464646464646464646464646";
+        let address = address_from_pubkey(&pubkey(pvk_hex).unwrap()).unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        // the compressed public key
+        let compressed = get_signingkey(pvk_hex)
+            .unwrap()
+            .verifying_key()
+            .to_encoded_point(true);
+        assert_eq!(
+            address_from_pubkey(&hex::encode(compressed.as_bytes())).unwrap(),
+            address
+        );
+
+        assert_eq!(
+            hash_personal_message(b"hello").to_string(),
+            "0x50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750"
+        );
+        let sig = personal_sign(pvk_hex, b"hello").unwrap();
+        assert!(verify_personal(address, b"hello", &sig).is_ok());
+        assert!(verify_personal(address, b"hello!", &sig).is_err());
+        assert!(verify_personal(Address::ZERO, b"hello", &sig).is_err());
+    }
 }
@@ -0,0 +1,122 @@
+use super::*;
+use std::marker::PhantomData;
+
+/// A double-ended queue stored in the key-value database.
+///
+/// The front and back positions are kept in atomic counters, so it can be used
+/// as a FIFO work queue shared by the handlers: every element is popped by only
+/// one of them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::collections::KvDeque;
+/// let jobs: KvDeque<String> = KvDeque::new("jobs");
+/// jobs.push_back(&"job-1".to_string())?;
+/// while let Some(job) = jobs.pop_front()? {
+///     // ...
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct KvDeque<T> {
+    name: String,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> KvDeque<T> {
+    /// Opens the queue named `name`.
+    pub fn new(name: &str) -> Self {
+        KvDeque {
+            name: name.to_string(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> Result<u64> {
+        let (head, tail) = self.bounds()?;
+        Ok((tail - head).max(0) as u64)
+    }
+
+    /// Returns `true` if the queue contains no elements.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Appends an element to the back.
+    pub fn push_back(&self, value: &T) -> Result<()> {
+        let index = kv::increment(&self.tail_key(), 1)? - 1;
+        store(&index_key(&self.name, index), value)
+    }
+
+    /// Prepends an element to the front.
+    pub fn push_front(&self, value: &T) -> Result<()> {
+        let index = kv::increment(&self.head_key(), -1)?;
+        store(&index_key(&self.name, index), value)
+    }
+
+    /// Retrieves the front element without removing it.
+    pub fn front(&self) -> Result<Option<T>> {
+        let (head, tail) = self.bounds()?;
+        if head >= tail {
+            return Ok(None);
+        }
+        load(&index_key(&self.name, head))
+    }
+
+    /// Retrieves the back element without removing it.
+    pub fn back(&self) -> Result<Option<T>> {
+        let (head, tail) = self.bounds()?;
+        if head >= tail {
+            return Ok(None);
+        }
+        load(&index_key(&self.name, tail - 1))
+    }
+
+    /// Removes the front element and returns it, `None` if the queue is empty.
+    pub fn pop_front(&self) -> Result<Option<T>> {
+        for _ in 0..MAX_RETRIES {
+            let (head, tail) = self.bounds()?;
+            if head >= tail {
+                return Ok(None);
+            }
+            let key = index_key(&self.name, head);
+            if let Some(value) = take(&Host, &key, &self.head_key(), head, head + 1)? {
+                return Ok(Some(serde_json::from_slice(&value)?));
+            }
+        }
+        bail!("the queue `{}` is modified concurrently", self.name)
+    }
+
+    /// Removes the back element and returns it, `None` if the queue is empty.
+    pub fn pop_back(&self) -> Result<Option<T>> {
+        for _ in 0..MAX_RETRIES {
+            let (head, tail) = self.bounds()?;
+            if head >= tail {
+                return Ok(None);
+            }
+            let key = index_key(&self.name, tail - 1);
+            if let Some(value) = take(&Host, &key, &self.tail_key(), tail, tail - 1)? {
+                return Ok(Some(serde_json::from_slice(&value)?));
+            }
+        }
+        bail!("the queue `{}` is modified concurrently", self.name)
+    }
+
+    /// Iterates over the elements from front to back.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T>> {
+        decode_elements(&self.name).map(|entry| entry.map(|(_, value)| value))
+    }
+
+    fn bounds(&self) -> Result<(i64, i64)> {
+        Ok((counter(&self.head_key())?, counter(&self.tail_key())?))
+    }
+
+    fn head_key(&self) -> String {
+        meta_key(&self.name, "head")
+    }
+
+    fn tail_key(&self) -> String {
+        meta_key(&self.name, "tail")
+    }
+}
@@ -0,0 +1,157 @@
+use super::*;
+use std::marker::PhantomData;
+
+/// A map stored in the key-value database.
+///
+/// Each entry is stored under a key derived from the `json` encoding of its key,
+/// and the entries are iterated in the order of those encoded keys.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::collections::KvMap;
+/// let owners: KvMap<u64, String> = KvMap::new("owners");
+/// owners.insert(&42, &"0x83c9fb5da807e4427b59b6c90b545496394abf98".to_string())?;
+/// let owner = owners.get(&42)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct KvMap<K, V> {
+    name: String,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> KvMap<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// Opens the map named `name`.
+    pub fn new(name: &str) -> Self {
+        KvMap {
+            name: name.to_string(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> Result<u64> {
+        Ok(counter(&self.len_key())? as u64)
+    }
+
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Retrieves the value for a key.
+    pub fn get(&self, key: &K) -> Result<Option<V>> {
+        load(&self.entry_key(key)?)
+    }
+
+    /// Returns `true` if the map contains the key.
+    pub fn contains_key(&self, key: &K) -> Result<bool> {
+        Ok(lookup(&self.entry_key(key)?)?.is_some())
+    }
+
+    /// Sets the value for a key, returns `true` if the key is new.
+    pub fn insert(&self, key: &K, value: &V) -> Result<bool> {
+        self.insert_with(&Host, key, value)
+    }
+
+    /// Removes a key and returns its value.
+    pub fn remove(&self, key: &K) -> Result<Option<V>> {
+        self.remove_with(&Host, key)
+    }
+
+    /// Iterates over the entries.
+    pub fn iter(&self) -> impl Iterator<Item = Result<(K, V)>> {
+        let prefix_len = element_prefix(&self.name).len();
+        decode_elements(&self.name).map(move |entry| {
+            let (key, value) = entry?;
+            Ok((serde_json::from_str(&key[prefix_len..])?, value))
+        })
+    }
+
+    /// Iterates over the keys.
+    pub fn keys(&self) -> impl Iterator<Item = Result<K>> {
+        self.iter().map(|entry| entry.map(|(key, _)| key))
+    }
+
+    /// Iterates over the values.
+    pub fn values(&self) -> impl Iterator<Item = Result<V>> {
+        self.iter().map(|entry| entry.map(|(_, value)| value))
+    }
+
+    fn insert_with(&self, store: &impl Store, key: &K, value: &V) -> Result<bool> {
+        let entry_key = self.entry_key(key)?;
+        let encoded = serde_json::to_vec(value)?;
+        for _ in 0..MAX_RETRIES {
+            let current = store.lookup(&entry_key)?;
+            let new = current.as_deref().is_none_or(is_removed);
+            if store.compare_and_swap(&entry_key, current.as_deref(), encoded.clone())? {
+                if new {
+                    store.increment(&self.len_key(), 1)?;
+                }
+                return Ok(new);
+            }
+        }
+        bail!("the map `{}` is modified concurrently", self.name)
+    }
+
+    // The removed entry is left blank rather than deleted, as a concurrent
+    // insert of the key may have replaced it already. The blank is reused by
+    // the next insert of the key.
+    fn remove_with(&self, store: &impl Store, key: &K) -> Result<Option<V>> {
+        let entry_key = self.entry_key(key)?;
+        for _ in 0..MAX_RETRIES {
+            let Some(value) = store.lookup(&entry_key)?.filter(|v| !is_removed(v)) else {
+                return Ok(None);
+            };
+            // only the removal blanking the entry takes it off the length
+            if store.compare_and_swap(&entry_key, Some(&value), Vec::new())? {
+                store.increment(&self.len_key(), -1)?;
+                return Ok(Some(serde_json::from_slice(&value)?));
+            }
+        }
+        bail!("the map `{}` is modified concurrently", self.name)
+    }
+
+    fn entry_key(&self, key: &K) -> Result<String> {
+        Ok(element_prefix(&self.name) + &serde_json::to_string(key)?)
+    }
+
+    fn len_key(&self) -> String {
+        meta_key(&self.name, "len")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::Memory;
+    use super::*;
+
+    #[test]
+    fn test_remove_insert_interleaved() {
+        let store = Memory::default();
+        let map: KvMap<u32, String> = KvMap::new("m");
+        assert!(map.insert_with(&store, &1, &"a".to_string()).unwrap());
+        assert!(!map.insert_with(&store, &1, &"b".to_string()).unwrap());
+
+        // an insert between blanking the entry and taking it off the length
+        // is kept and counted
+        store.before("increment", |store| {
+            let map: KvMap<u32, String> = KvMap::new("m");
+            assert!(map.insert_with(store, &1, &"c".to_string()).unwrap());
+        });
+        assert_eq!(map.remove_with(&store, &1).unwrap(), Some("b".to_string()));
+        let entry_key = map.entry_key(&1).unwrap();
+        assert_eq!(store.get(&entry_key), Some(b"\"c\"".to_vec()));
+        assert_eq!(store.get("m#len"), Some(b"1".to_vec()));
+
+        assert_eq!(map.remove_with(&store, &1).unwrap(), Some("c".to_string()));
+        assert_eq!(map.remove_with(&store, &1).unwrap(), None);
+        assert_eq!(store.get("m#len"), Some(b"0".to_vec()));
+        assert!(map.insert_with(&store, &1, &"d".to_string()).unwrap());
+        assert_eq!(store.get("m#len"), Some(b"1".to_vec()));
+    }
+}
@@ -0,0 +1,228 @@
+//! The collections are lazily loaded: creating one doesn't touch the database,
+//! and each operation only reads or writes the keys it needs. The elements are
+//! stored one per key under keys derived from the collection name, and encoded
+//! in `json` with `serde`.
+//!
+//! The metadata of a collection named `name` is kept under `name#...`, and its
+//! elements under `name:...`. Different collections should use different names,
+//! and no other key should start with `name:`.
+mod deque;
+pub use self::deque::*;
+mod map;
+pub use self::map::*;
+mod set;
+pub use self::set::*;
+mod vec;
+pub use self::vec::*;
+
+use super::kv;
+use anyhow::{bail, Result};
+use serde::{de::DeserializeOwned, Serialize};
+
+// Retries of an update when another handler modifies the same key first.
+const MAX_RETRIES: usize = 8;
+
+fn meta_key(name: &str, field: &str) -> String {
+    format!("{}#{}", name, field)
+}
+
+fn element_prefix(name: &str) -> String {
+    format!("{}:", name)
+}
+
+// Maps the signed index into an order-preserving fixed width key, so that
+// scanning the elements visits them in index order.
+fn index_key(name: &str, index: i64) -> String {
+    format!("{}:{:016x}", name, (index as u64) ^ (1 << 63))
+}
+
+fn counter(key: &str) -> Result<i64> {
+    kv::increment(key, 0)
+}
+
+// A removed element is blanked, so that only one of the concurrent removals
+// takes it. The elements are never empty, as they're in `json`.
+fn is_removed(value: &[u8]) -> bool {
+    value.is_empty()
+}
+
+fn lookup(key: &str) -> Result<Option<Vec<u8>>> {
+    Ok(kv::lookup(key)?.filter(|value| !is_removed(value)))
+}
+
+// The operations of the key-value database which the elements are added and
+// taken with, so that the interleavings of the handlers are tested in memory.
+trait Store {
+    fn lookup(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    fn set(&self, key: &str, value: Vec<u8>) -> Result<()>;
+    fn delete(&self, key: &str) -> Result<()>;
+    fn increment(&self, key: &str, delta: i64) -> Result<i64>;
+    fn compare_and_swap(&self, key: &str, expected: Option<&[u8]>, new: Vec<u8>) -> Result<bool>;
+}
+
+struct Host;
+
+impl Store for Host {
+    fn lookup(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        kv::lookup(key)
+    }
+
+    fn set(&self, key: &str, value: Vec<u8>) -> Result<()> {
+        kv::set(key, value)
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        kv::delete(key)
+    }
+
+    fn increment(&self, key: &str, delta: i64) -> Result<i64> {
+        kv::increment(key, delta)
+    }
+
+    fn compare_and_swap(&self, key: &str, expected: Option<&[u8]>, new: Vec<u8>) -> Result<bool> {
+        kv::compare_and_swap(key, expected, new)
+    }
+}
+
+// Takes the element at `key` off the collection by moving the counter at
+// `counter` from `current` to `next`. The element is blanked so that no other
+// handler takes it, and deleted before the counter moves, so that a push given
+// the slot afterwards is never erased. It's put back if another handler moved
+// the counter first, and `None` is returned to retry, as it is for a missing
+// element, which may not be stored yet by its pusher.
+fn take(
+    store: &impl Store,
+    key: &str,
+    counter: &str,
+    current: i64,
+    next: i64,
+) -> Result<Option<Vec<u8>>> {
+    let Some(value) = store.lookup(key)?.filter(|value| !is_removed(value)) else {
+        return Ok(None);
+    };
+    if !store.compare_and_swap(key, Some(&value), Vec::new())? {
+        return Ok(None);
+    }
+    store.delete(key)?;
+    let moved = store.compare_and_swap(
+        counter,
+        Some(current.to_string().as_bytes()),
+        next.to_string().into_bytes(),
+    )?;
+    if moved {
+        return Ok(Some(value));
+    }
+    store.compare_and_swap(key, None, value)?;
+    Ok(None)
+}
+
+fn load<T: DeserializeOwned>(key: &str) -> Result<Option<T>> {
+    match lookup(key)? {
+        Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+        None => Ok(None),
+    }
+}
+
+fn store<T: Serialize>(key: &str, value: &T) -> Result<()> {
+    kv::set(key, serde_json::to_vec(value)?)
+}
+
+fn decode_elements<T: DeserializeOwned>(name: &str) -> impl Iterator<Item = Result<(String, T)>> {
+    kv::Scan::prefix(&element_prefix(name))
+        .into_iter()
+        .filter(|entry| !matches!(entry, Ok((_, value)) if is_removed(value)))
+        .map(|entry| {
+            let (key, value) = entry?;
+            Ok((key, serde_json::from_slice(&value)?))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    type Hook = Box<dyn FnOnce(&Memory)>;
+
+    // A store in memory, which runs a hook once before an operation, as
+    // another handler would in between the steps of an update.
+    #[derive(Default)]
+    pub(super) struct Memory {
+        entries: RefCell<BTreeMap<String, Vec<u8>>>,
+        hook: RefCell<Option<(&'static str, Hook)>>,
+    }
+
+    impl Memory {
+        pub fn before(&self, op: &'static str, hook: impl FnOnce(&Memory) + 'static) {
+            *self.hook.borrow_mut() = Some((op, Box::new(hook)));
+        }
+
+        pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+            self.entries.borrow().get(key).cloned()
+        }
+
+        fn run_hook(&self, op: &str) {
+            let hook = self.hook.borrow_mut().take_if(|(name, _)| *name == op);
+            if let Some((_, hook)) = hook {
+                hook(self);
+            }
+        }
+    }
+
+    impl Store for Memory {
+        fn lookup(&self, key: &str) -> Result<Option<Vec<u8>>> {
+            self.run_hook("lookup");
+            Ok(self.get(key))
+        }
+
+        fn set(&self, key: &str, value: Vec<u8>) -> Result<()> {
+            self.run_hook("set");
+            self.entries.borrow_mut().insert(key.to_string(), value);
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<()> {
+            self.run_hook("delete");
+            self.entries.borrow_mut().remove(key);
+            Ok(())
+        }
+
+        fn increment(&self, key: &str, delta: i64) -> Result<i64> {
+            self.run_hook("increment");
+            let value = match self.get(key) {
+                Some(value) => String::from_utf8(value)?.parse::<i64>()? + delta,
+                None => delta,
+            };
+            let mut entries = self.entries.borrow_mut();
+            entries.insert(key.to_string(), value.to_string().into_bytes());
+            Ok(value)
+        }
+
+        fn compare_and_swap(
+            &self,
+            key: &str,
+            expected: Option<&[u8]>,
+            new: Vec<u8>,
+        ) -> Result<bool> {
+            self.run_hook("compare_and_swap");
+            if self.get(key).as_deref() != expected {
+                return Ok(false);
+            }
+            self.entries.borrow_mut().insert(key.to_string(), new);
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn test_index_key_order() {
+        let keys: Vec<String> = [-2, -1, 0, 1, 255, 256]
+            .iter()
+            .map(|i| index_key("q", *i))
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert_eq!(index_key("q", 0), "q:8000000000000000");
+    }
+}
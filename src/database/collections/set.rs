@@ -0,0 +1,56 @@
+use super::*;
+
+/// A set stored in the key-value database.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::collections::KvSet;
+/// let devices: KvSet<String> = KvSet::new("devices");
+/// if devices.insert(&"dev-1".to_string())? {
+///     // first message from this device
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct KvSet<T> {
+    map: KvMap<T, ()>,
+}
+
+impl<T: Serialize + DeserializeOwned> KvSet<T> {
+    /// Opens the set named `name`.
+    pub fn new(name: &str) -> Self {
+        KvSet {
+            map: KvMap::new(name),
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> Result<u64> {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    pub fn is_empty(&self) -> Result<bool> {
+        self.map.is_empty()
+    }
+
+    /// Returns `true` if the set contains the element.
+    pub fn contains(&self, value: &T) -> Result<bool> {
+        self.map.contains_key(value)
+    }
+
+    /// Adds an element, returns `true` if it's new.
+    pub fn insert(&self, value: &T) -> Result<bool> {
+        self.map.insert(value, &())
+    }
+
+    /// Removes an element, returns `true` if it was present.
+    pub fn remove(&self, value: &T) -> Result<bool> {
+        Ok(self.map.remove(value)?.is_some())
+    }
+
+    /// Iterates over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T>> {
+        self.map.keys()
+    }
+}
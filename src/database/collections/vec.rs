@@ -0,0 +1,139 @@
+use super::*;
+use std::marker::PhantomData;
+
+/// A growable list stored in the key-value database.
+///
+/// The length is kept in an atomic counter, so concurrent pushes from
+/// different handlers never overwrite each other.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::collections::KvVec;
+/// let readings: KvVec<f64> = KvVec::new("dev:1:readings");
+/// readings.push(&21.5)?;
+/// let first = readings.get(0)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct KvVec<T> {
+    name: String,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> KvVec<T> {
+    /// Opens the list named `name`.
+    pub fn new(name: &str) -> Self {
+        KvVec {
+            name: name.to_string(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> Result<u64> {
+        Ok(counter(&self.len_key())? as u64)
+    }
+
+    /// Returns `true` if the list contains no elements.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Retrieves the element at `index`, `None` if it's out of bounds.
+    pub fn get(&self, index: u64) -> Result<Option<T>> {
+        if index >= self.len()? {
+            return Ok(None);
+        }
+        load(&index_key(&self.name, index as _))
+    }
+
+    /// Replaces the element at `index`.
+    pub fn set(&self, index: u64, value: &T) -> Result<()> {
+        let key = index_key(&self.name, index as _);
+        let encoded = serde_json::to_vec(value)?;
+        for _ in 0..MAX_RETRIES {
+            if index >= self.len()? {
+                bail!("the index {} is out of bounds", index);
+            }
+            // the element may not be stored yet by its pusher, or being taken
+            // by a popper
+            let Some(current) = lookup(&key)? else {
+                continue;
+            };
+            if kv::compare_and_swap(&key, Some(&current), encoded.clone())? {
+                return Ok(());
+            }
+        }
+        bail!("the list `{}` is modified concurrently", self.name)
+    }
+
+    /// Appends an element to the back and returns its index.
+    pub fn push(&self, value: &T) -> Result<u64> {
+        self.push_with(&Host, value)
+    }
+
+    /// Removes the last element and returns it, `None` if the list is empty.
+    pub fn pop(&self) -> Result<Option<T>> {
+        self.pop_with(&Host)
+    }
+
+    /// Iterates over the elements in order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T>> {
+        decode_elements(&self.name).map(|entry| entry.map(|(_, value)| value))
+    }
+
+    fn push_with(&self, store: &impl Store, value: &T) -> Result<u64> {
+        let index = store.increment(&self.len_key(), 1)? - 1;
+        store.set(&index_key(&self.name, index), serde_json::to_vec(value)?)?;
+        Ok(index as u64)
+    }
+
+    fn pop_with(&self, store: &impl Store) -> Result<Option<T>> {
+        for _ in 0..MAX_RETRIES {
+            let len = store.increment(&self.len_key(), 0)?;
+            if len == 0 {
+                return Ok(None);
+            }
+            let key = index_key(&self.name, len - 1);
+            if let Some(value) = take(store, &key, &self.len_key(), len, len - 1)? {
+                return Ok(Some(serde_json::from_slice(&value)?));
+            }
+        }
+        bail!("the list `{}` is modified concurrently", self.name)
+    }
+
+    fn len_key(&self) -> String {
+        meta_key(&self.name, "len")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::Memory;
+    use super::*;
+
+    #[test]
+    fn test_pop_push_interleaved() {
+        let store = Memory::default();
+        let list: KvVec<u32> = KvVec::new("l");
+        list.push_with(&store, &1).unwrap();
+        list.push_with(&store, &2).unwrap();
+
+        // a push between taking the last element and moving the length gets
+        // the next slot, and the pop retries with the pushed element
+        store.before("delete", |store| {
+            KvVec::<u32>::new("l").push_with(store, &3).unwrap();
+        });
+        assert_eq!(list.pop_with(&store).unwrap(), Some(3));
+        assert_eq!(store.get(&index_key("l", 1)), Some(b"2".to_vec()));
+        assert_eq!(store.get(&index_key("l", 2)), None);
+
+        // a push right after the pop reuses the slot and keeps its element
+        assert_eq!(list.pop_with(&store).unwrap(), Some(2));
+        list.push_with(&store, &4).unwrap();
+        assert_eq!(store.get(&index_key("l", 1)), Some(b"4".to_vec()));
+        assert_eq!(list.pop_with(&store).unwrap(), Some(4));
+        assert_eq!(list.pop_with(&store).unwrap(), Some(1));
+        assert_eq!(list.pop_with(&store).unwrap(), None);
+    }
+}
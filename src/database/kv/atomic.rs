@@ -0,0 +1,118 @@
+use super::store::get_raw;
+use super::ttl::envelope_matches;
+use crate::host::abi::*;
+use anyhow::{bail, Result};
+
+/// Atomically adds `delta` to the counter stored under the key and returns the new value.
+///
+/// The counter is stored as a decimal string, so it can also be read with
+/// [`get`](super::get). A missing key is treated as `0`.
+/// The update is done by the host, so concurrent handlers never lose an increment.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::increment;
+/// let seq = increment("dev:1:seq", 1)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn increment(key: &str, delta: i64) -> Result<i64> {
+    let value = &mut 0_i64 as *const i64;
+    match unsafe { ws_incr_db(key.as_bytes().as_ptr(), key.len() as _, delta, value) } {
+        0 => Ok(unsafe { *value }),
+        _ => bail!("fail to increment the value by the key"),
+    }
+}
+
+/// Atomically sets the value for a key if its current value equals `expected`.
+///
+/// `None` as `expected` means the key must not exist.
+/// Returns `true` if the value is swapped, and `false` if the current value
+/// doesn't match, in which case nothing is written.
+///
+/// A value written by [`set_with_ttl`](super::set_with_ttl) is compared without
+/// its expiry, and an expired one is compared as a missing key. The new value
+/// doesn't expire.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::compare_and_swap;
+/// let swapped = compare_and_swap("owner", Some(b"alice"), b"bob".to_vec())?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn compare_and_swap(key: &str, expected: Option<&[u8]>, new: Vec<u8>) -> Result<bool> {
+    if compare_and_swap_raw(key, expected, &new)? {
+        return Ok(true);
+    }
+    // the value may be in an envelope of the ttl, whose raw bytes are compared
+    // by the host again, so that it's still swapped atomically
+    let raw = match get_raw(key) {
+        Ok(raw) => raw,
+        Err(_) => return Ok(false),
+    };
+    if !envelope_matches(&raw, expected)? {
+        return Ok(false);
+    }
+    compare_and_swap_raw(key, Some(&raw), &new)
+}
+
+fn compare_and_swap_raw(key: &str, expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+    let (expected_ptr, expected_size) = expected_parts(expected);
+    match unsafe {
+        ws_cas_db(
+            key.as_bytes().as_ptr(),
+            key.len() as _,
+            expected_ptr,
+            expected_size,
+            new.as_ptr(),
+            new.len() as _,
+        )
+    } {
+        0 => Ok(true),
+        1 => Ok(false),
+        _ => bail!("fail to compare and swap the value by the key"),
+    }
+}
+
+// A missing key is passed to the host as a negative size, so that it's told
+// apart from an empty value.
+fn expected_parts(expected: Option<&[u8]>) -> (*const u8, i32) {
+    match expected {
+        Some(v) => (v.as_ptr(), v.len() as i32),
+        None => (std::ptr::null(), -1),
+    }
+}
+
+/// Atomically sets the value for a key only if the key doesn't exist.
+///
+/// Returns `true` if the value is set. An expired key written by
+/// [`set_with_ttl`](super::set_with_ttl) is treated as missing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::set_if_absent;
+/// if set_if_absent("nonce:42", vec![1])? {
+///     // first time seeing this nonce
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn set_if_absent(key: &str, value: Vec<u8>) -> Result<bool> {
+    compare_and_swap(key, None, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_parts() {
+        assert_eq!(expected_parts(None), (std::ptr::null(), -1));
+        let (ptr, size) = expected_parts(Some(b""));
+        assert!(!ptr.is_null());
+        assert_eq!(size, 0);
+        let value = b"alice";
+        assert_eq!(expected_parts(Some(value)), (value.as_ptr(), 5));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::host::abi::*;
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::Serialize;
+use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
+
+#[skip_serializing_none]
+#[derive(Serialize)]
+struct BatchOp<'a> {
+    key: &'a str,
+    value: Option<String>, // base64 encoding, `None` for deletion
+}
+
+#[derive(Serialize)]
+struct BatchRequest<'a> {
+    ops: Vec<BatchOp<'a>>,
+}
+
+/// A batch of writes to the key-value database.
+///
+/// The writes are buffered in the wasm and sent to the host in one call by
+/// [`Batch::commit`]. The host applies them atomically if its store supports
+/// it. Reading through the batch returns the pending writes first.
+/// Dropping a batch without committing discards all the writes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::Batch;
+/// let mut batch = Batch::new();
+/// batch.set("dev:1:status", b"online".to_vec());
+/// batch.delete("dev:1:error");
+/// assert_eq!(batch.get("dev:1:status")?, Some(b"online".to_vec()));
+/// batch.commit()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Default)]
+pub struct Batch {
+    pending: BTreeMap<String, Option<Vec<u8>>>,
+}
+
+impl Batch {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffers setting the value for a key.
+    pub fn set(&mut self, key: &str, value: Vec<u8>) {
+        self.pending.insert(key.to_string(), Some(value));
+    }
+
+    /// Buffers deleting a key.
+    pub fn delete(&mut self, key: &str) {
+        self.pending.insert(key.to_string(), None);
+    }
+
+    /// Retrieves the value for a key, seeing the pending writes of the batch.
+    ///
+    /// Returns `None` if the key is deleted in the batch, or missing or expired
+    /// in the database.
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        self.get_with(key, super::lookup)
+    }
+
+    fn get_with(
+        &self,
+        key: &str,
+        lookup: impl FnOnce(&str) -> Result<Option<Vec<u8>>>,
+    ) -> Result<Option<Vec<u8>>> {
+        match self.pending.get(key) {
+            Some(value) => Ok(value.clone()),
+            None => lookup(key),
+        }
+    }
+
+    /// Returns the number of the pending writes.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if there is no pending write.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Sends all the pending writes to the host in one call.
+    pub fn commit(self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let encoded = self.encode()?;
+        match unsafe { ws_write_db_batch(encoded.as_ptr(), encoded.len() as _) } {
+            0 => Ok(()),
+            _ => bail!("fail to commit the batch"),
+        }
+    }
+
+    fn encode(&self) -> Result<String> {
+        let req = BatchRequest {
+            ops: self
+                .pending
+                .iter()
+                .map(|(key, value)| BatchOp {
+                    key,
+                    value: value.as_ref().map(|v| general_purpose::STANDARD.encode(v)),
+                })
+                .collect(),
+        };
+        Ok(serde_json::to_string(&req)?)
+    }
+}
+
+/// Runs `f` with a new [`Batch`], committing it if `f` returns `Ok` and
+/// discarding it otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::batch;
+/// batch(|b| {
+///     for i in 0..50 {
+///         b.set(&format!("dev:1:{}", i), vec![]);
+///     }
+///     Ok(())
+/// })?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn batch<T>(f: impl FnOnce(&mut Batch) -> Result<T>) -> Result<T> {
+    let mut b = Batch::new();
+    let ret = f(&mut b)?;
+    b.commit()?;
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let mut batch = Batch::new();
+        batch.set("b", b"value".to_vec());
+        batch.delete("a");
+        batch.set("c", vec![]);
+        assert_eq!(
+            batch.encode().unwrap(),
+            r#"{"ops":[{"key":"a"},{"key":"b","value":"dmFsdWU="},{"key":"c","value":""}]}"#
+        );
+
+        // the last write to a key wins
+        batch.set("a", b"value".to_vec());
+        batch.delete("b");
+        assert_eq!(
+            batch.encode().unwrap(),
+            r#"{"ops":[{"key":"a","value":"dmFsdWU="},{"key":"b"},{"key":"c","value":""}]}"#
+        );
+        assert_eq!(batch.len(), 3);
+    }
+
+    #[test]
+    fn test_get_pending() {
+        let mut batch = Batch::new();
+        batch.set("set", b"pending".to_vec());
+        batch.delete("deleted");
+        let stored = |key: &str| match key {
+            "missing" => Ok(None),
+            _ => Ok(Some(b"stored".to_vec())),
+        };
+        assert_eq!(
+            batch.get_with("set", stored).unwrap(),
+            Some(b"pending".to_vec())
+        );
+        assert_eq!(batch.get_with("deleted", stored).unwrap(), None);
+        assert_eq!(
+            batch.get_with("other", stored).unwrap(),
+            Some(b"stored".to_vec())
+        );
+        assert_eq!(batch.get_with("missing", stored).unwrap(), None);
+    }
+}
@@ -0,0 +1,10 @@
+mod store;
+pub use self::store::*;
+mod scan;
+pub use self::scan::*;
+mod atomic;
+pub use self::atomic::*;
+mod ttl;
+pub use self::ttl::*;
+mod batch;
+pub use self::batch::*;
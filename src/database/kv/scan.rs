@@ -0,0 +1,269 @@
+use super::ttl::{open_envelope, Envelope};
+use crate::host::abi::*;
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::VecDeque;
+
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+#[skip_serializing_none]
+#[derive(Serialize, Clone, Default)]
+struct ScanRequest {
+    prefix: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    limit: u32,
+    cursor: Option<String>,
+    reverse: bool,
+}
+
+#[derive(Deserialize)]
+struct ScanResponse {
+    #[serde(default)]
+    entries: Vec<ScanEntry>,
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ScanEntry {
+    key: String,
+    value: String, // base64 encoding
+}
+
+/// A page of key-value pairs returned by a [`Scan`].
+pub struct Page {
+    /// The key-value pairs in key order.
+    pub entries: Vec<(String, Vec<u8>)>,
+    /// The continuation token of the next page, `None` if this is the last page.
+    pub next: Option<String>,
+}
+
+/// An ordered scan over the keys of the key-value database.
+///
+/// The keys are visited in lexicographic byte order, page by page. Each page
+/// holds at most `limit` pairs and carries a continuation token which can be
+/// passed to [`Scan::cursor`] to resume the scan later, e.g. in another event.
+/// The expired values written by [`set_with_ttl`](super::set_with_ttl) are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::Scan;
+/// let page = Scan::prefix("dev:1:").limit(50).page()?;
+/// for (key, value) in page.entries {
+///     // ...
+/// }
+/// if let Some(token) = page.next {
+///     let next_page = Scan::prefix("dev:1:").limit(50).cursor(&token).page()?;
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct Scan {
+    req: ScanRequest,
+}
+
+impl Scan {
+    /// Scans all the keys starting with `prefix`.
+    pub fn prefix(prefix: &str) -> Self {
+        Scan {
+            req: ScanRequest {
+                prefix: Some(prefix.to_string()),
+                limit: DEFAULT_PAGE_SIZE,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Scans all the keys in the range `[start, end)`.
+    pub fn range(start: &str, end: &str) -> Self {
+        Scan {
+            req: ScanRequest {
+                start: Some(start.to_string()),
+                end: Some(end.to_string()),
+                limit: DEFAULT_PAGE_SIZE,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Sets the maximum number of pairs returned in one page.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.req.limit = limit;
+        self
+    }
+
+    /// Resumes the scan from the continuation token of a previous page.
+    pub fn cursor(mut self, token: &str) -> Self {
+        self.req.cursor = Some(token.to_string());
+        self
+    }
+
+    /// Visits the keys in descending order.
+    pub fn reverse(mut self) -> Self {
+        self.req.reverse = true;
+        self
+    }
+
+    /// Fetches one page of the scan from the host.
+    pub fn page(&self) -> Result<Page> {
+        if self.req.limit == 0 {
+            bail!("the limit of the scan should be positive");
+        }
+        let encoded = serde_json::to_string(&self.req)?;
+        let data_ptr = &mut 0_i32 as *const _ as *const *mut u8;
+        let data_size = &mut 0_i32 as *const i32;
+        let ret = match unsafe {
+            ws_scan_db(encoded.as_ptr(), encoded.len() as _, data_ptr, data_size)
+        } {
+            0 => unsafe { Vec::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) },
+            _ => bail!("fail to scan the keys"),
+        };
+        decode_page(&ret)
+    }
+}
+
+fn decode_page(ret: &[u8]) -> Result<Page> {
+    let resp: ScanResponse = serde_json::from_slice(ret)?;
+    let mut entries = Vec::with_capacity(resp.entries.len());
+    for e in resp.entries {
+        let value = general_purpose::STANDARD.decode(e.value)?;
+        match open_envelope(&value)? {
+            Envelope::Plain => entries.push((e.key, value)),
+            Envelope::Live(value) => entries.push((e.key, value)),
+            Envelope::Expired => {}
+        }
+    }
+    Ok(Page {
+        entries,
+        next: resp.next,
+    })
+}
+
+impl IntoIterator for Scan {
+    type Item = Result<(String, Vec<u8>)>;
+    type IntoIter = ScanIter;
+
+    fn into_iter(self) -> ScanIter {
+        ScanIter {
+            scan: self,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+/// An iterator over all the pairs of a [`Scan`], fetching the pages lazily.
+pub struct ScanIter {
+    scan: Scan,
+    buffer: VecDeque<(String, Vec<u8>)>,
+    done: bool,
+}
+
+impl Iterator for ScanIter {
+    type Item = Result<(String, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.buffer.is_empty() && !self.done {
+            let page = match self.scan.page() {
+                Ok(page) => page,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            };
+            self.buffer.extend(page.entries);
+            match page.next {
+                Some(token) => self.scan.req.cursor = Some(token),
+                None => self.done = true,
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+// Retrieves the value for a key, `None` if it's missing or expired. Unlike
+// `get`, a missing key is told apart from a failure of the host.
+pub(crate) fn lookup(key: &str) -> Result<Option<Vec<u8>>> {
+    let page = Scan::range(key, &format!("{}\0", key)).limit(1).page()?;
+    Ok(page.entries.into_iter().next().map(|(_, value)| value))
+}
+
+/// Retrieves all the key-value pairs whose keys start with `prefix`, in key order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::scan;
+/// let history = scan("dev:1:")?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn scan(prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+    Scan::prefix(prefix).into_iter().collect()
+}
+
+/// Retrieves all the key-value pairs whose keys are in the range `[start, end)`, in key order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::range;
+/// let records = range("dev:1:1680000000", "dev:1:1690000000")?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn range(start: &str, end: &str) -> Result<Vec<(String, Vec<u8>)>> {
+    Scan::range(start, end).into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_request() {
+        let scan = Scan::range("dev:1:1680000000", "dev:1:1690000000")
+            .limit(10)
+            .reverse();
+        assert_eq!(
+            serde_json::to_string(&scan.req).unwrap(),
+            r#"{"start":"dev:1:1680000000","end":"dev:1:1690000000","limit":10,"reverse":true}"#
+        );
+        let scan = Scan::prefix("dev:1:").cursor("token");
+        assert_eq!(
+            serde_json::to_string(&scan.req).unwrap(),
+            r#"{"prefix":"dev:1:","limit":100,"cursor":"token","reverse":false}"#
+        );
+    }
+
+    #[test]
+    fn test_decode_page() {
+        let sealed = |expiry: u64, value: &[u8]| {
+            let mut raw = b"\0ws-ttl\0".to_vec();
+            raw.extend_from_slice(&expiry.to_be_bytes());
+            raw.extend_from_slice(value);
+            general_purpose::STANDARD.encode(raw)
+        };
+        let ret = serde_json::json!({
+            "entries": [
+                {"key": "a", "value": general_purpose::STANDARD.encode(b"plain")},
+                {"key": "b", "value": sealed(u64::MAX, b"live")},
+                {"key": "c", "value": sealed(0, b"expired")},
+            ],
+            "next": "token",
+        });
+        let page = decode_page(ret.to_string().as_bytes()).unwrap();
+        assert_eq!(
+            page.entries,
+            vec![
+                ("a".to_string(), b"plain".to_vec()),
+                ("b".to_string(), b"live".to_vec()),
+            ]
+        );
+        assert_eq!(page.next.as_deref(), Some("token"));
+
+        let page = decode_page(br#"{"next": null}"#).unwrap();
+        assert!(page.entries.is_empty() && page.next.is_none());
+    }
+}
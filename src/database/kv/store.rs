@@ -1,8 +1,13 @@
-use super::super::host::abi::*;
+use super::ttl::{open_envelope, Envelope};
+use crate::host::abi::*;
 use anyhow::{bail, Result};
 
 /// Retrieves the value for a key from the key-value database.
 ///
+/// A value written by [`set_with_ttl`](super::set_with_ttl) is returned without
+/// its expiry. Once expired, an error is returned as for a missing key; the
+/// key is left in place, as a concurrent write may have replaced it already.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -10,6 +15,15 @@ use anyhow::{bail, Result};
 /// let value = get(key)?;
 /// ```
 pub fn get(key: &str) -> Result<Vec<u8>> {
+    let value = get_raw(key)?;
+    match open_envelope(&value)? {
+        Envelope::Plain => Ok(value),
+        Envelope::Live(value) => Ok(value),
+        Envelope::Expired => bail!("the value of the key has expired"),
+    }
+}
+
+pub(super) fn get_raw(key: &str) -> Result<Vec<u8>> {
     let data_ptr = &mut (0 as i32) as *const _ as *const *mut u8;
     let data_size = &mut (0 as i32) as *const i32;
     match unsafe { ws_get_db(key.as_bytes().as_ptr(), key.len() as _, data_ptr, data_size) } {
@@ -39,3 +53,19 @@ pub fn set(key: &str, value: Vec<u8>) -> Result<()> {
         _ => bail!("fail to set the value by the key"),
     }
 }
+
+/// Deletes a key and its value from the key-value database.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::kv::delete;
+/// delete("key")?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn delete(key: &str) -> Result<()> {
+    match unsafe { ws_del_db(key.as_bytes().as_ptr(), key.len() as _) } {
+        0 => Ok(()),
+        _ => bail!("fail to delete the key"),
+    }
+}
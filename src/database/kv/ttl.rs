@@ -0,0 +1,171 @@
+#[cfg(feature = "host-ttl")]
+use crate::host::abi::*;
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+#[cfg(not(all(target_arch = "wasm32", not(target_os = "wasi"))))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// An envelope is laid out as `magic | expiry | value`, where the expiry is
+// the unix timestamp in seconds encoded as big-endian u64.
+const ENVELOPE_MAGIC: &[u8] = b"\0ws-ttl\0";
+const ENVELOPE_HEADER_SIZE: usize = ENVELOPE_MAGIC.len() + 8;
+
+pub(super) enum Envelope {
+    Plain,
+    Live(Vec<u8>),
+    Expired,
+}
+
+/// Sets the value for a key in the key-value database, which expires after `ttl`.
+///
+/// The expired value is no longer returned by [`get`](super::get) or the scans.
+/// The ttl is rounded up to whole seconds.
+///
+/// When the `host-ttl` feature is enabled, the expiry is enforced by the host,
+/// which removes the expired keys by itself. Otherwise the value is stored with
+/// its expiry timestamp, and the expired key is treated as missing until it's
+/// written again.
+/// The latter needs a clock on the target, e.g. `wasm32-wasi`; an error is
+/// returned on `wasm32-unknown-unknown`, which has none.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use ws_sdk::database::kv::set_with_ttl;
+/// set_with_ttl("nonce:42", vec![1], Duration::from_secs(600))?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn set_with_ttl(key: &str, value: Vec<u8>, ttl: Duration) -> Result<()> {
+    set_expiring(key, value, ttl_secs(ttl)?)
+}
+
+fn ttl_secs(ttl: Duration) -> Result<u64> {
+    let ttl_secs = ttl
+        .as_secs()
+        .saturating_add(u64::from(ttl.subsec_nanos() > 0));
+    if ttl_secs == 0 {
+        bail!("the ttl should be positive");
+    }
+    Ok(ttl_secs)
+}
+
+#[cfg(feature = "host-ttl")]
+fn set_expiring(key: &str, value: Vec<u8>, ttl_secs: u64) -> Result<()> {
+    let ttl_secs = i64::try_from(ttl_secs).context("the ttl is too long")?;
+    match unsafe {
+        ws_set_db_with_ttl(
+            key.as_bytes().as_ptr(),
+            key.len() as _,
+            value.as_ptr(),
+            value.len() as _,
+            ttl_secs,
+        )
+    } {
+        0 => Ok(()),
+        _ => bail!("fail to set the value with ttl by the key"),
+    }
+}
+
+#[cfg(not(feature = "host-ttl"))]
+fn set_expiring(key: &str, value: Vec<u8>, ttl_secs: u64) -> Result<()> {
+    let expiry = now_secs()?
+        .checked_add(ttl_secs)
+        .context("the ttl is too long")?;
+    super::set(key, seal(&value, expiry))
+}
+
+pub(super) fn open_envelope(raw: &[u8]) -> Result<Envelope> {
+    if !is_envelope(raw) {
+        return Ok(Envelope::Plain);
+    }
+    Ok(open_envelope_at(raw, now_secs()?))
+}
+
+// Tells if the raw value, which failed the comparison of the host, is an
+// envelope whose value is `expected`. An expired envelope stands for a missing
+// key, i.e. it matches `None`.
+pub(super) fn envelope_matches(raw: &[u8], expected: Option<&[u8]>) -> Result<bool> {
+    if !is_envelope(raw) {
+        return Ok(false);
+    }
+    Ok(envelope_matches_at(raw, expected, now_secs()?))
+}
+
+fn envelope_matches_at(raw: &[u8], expected: Option<&[u8]>, now: u64) -> bool {
+    match open_envelope_at(raw, now) {
+        Envelope::Plain => false,
+        Envelope::Live(value) => expected == Some(value.as_slice()),
+        Envelope::Expired => expected.is_none(),
+    }
+}
+
+fn is_envelope(raw: &[u8]) -> bool {
+    raw.len() >= ENVELOPE_HEADER_SIZE && raw.starts_with(ENVELOPE_MAGIC)
+}
+
+#[cfg_attr(feature = "host-ttl", allow(dead_code))]
+fn seal(value: &[u8], expiry: u64) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(ENVELOPE_HEADER_SIZE + value.len());
+    raw.extend_from_slice(ENVELOPE_MAGIC);
+    raw.extend_from_slice(&expiry.to_be_bytes());
+    raw.extend_from_slice(value);
+    raw
+}
+
+fn open_envelope_at(raw: &[u8], now: u64) -> Envelope {
+    if !is_envelope(raw) {
+        return Envelope::Plain;
+    }
+    let mut expiry = [0u8; 8];
+    expiry.copy_from_slice(&raw[ENVELOPE_MAGIC.len()..ENVELOPE_HEADER_SIZE]);
+    if now >= u64::from_be_bytes(expiry) {
+        return Envelope::Expired;
+    }
+    Envelope::Live(raw[ENVELOPE_HEADER_SIZE..].to_vec())
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+fn now_secs() -> Result<u64> {
+    bail!("no clock on this target, enable the `host-ttl` feature to use the ttl")
+}
+
+#[cfg(not(all(target_arch = "wasm32", not(target_os = "wasi"))))]
+fn now_secs() -> Result<u64> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("fail to get the current time")?
+        .as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope() {
+        let raw = seal(b"value", 100);
+        assert!(matches!(open_envelope_at(&raw, 99), Envelope::Live(v) if v == b"value"));
+        assert!(matches!(open_envelope_at(&raw, 100), Envelope::Expired));
+        assert!(matches!(open_envelope_at(b"value", 100), Envelope::Plain));
+    }
+
+    #[test]
+    fn test_envelope_matches() {
+        let raw = seal(b"value", 100);
+        assert!(envelope_matches_at(&raw, Some(b"value"), 99));
+        assert!(!envelope_matches_at(&raw, Some(b"other"), 99));
+        assert!(!envelope_matches_at(&raw, None, 99));
+        assert!(envelope_matches_at(&raw, None, 100));
+        assert!(!envelope_matches_at(&raw, Some(b"value"), 100));
+        assert!(!envelope_matches_at(b"value", Some(b"value"), 99));
+    }
+
+    #[test]
+    fn test_ttl_secs() {
+        assert_eq!(ttl_secs(Duration::from_millis(1500)).unwrap(), 2);
+        assert_eq!(ttl_secs(Duration::from_secs(60)).unwrap(), 60);
+        assert_eq!(ttl_secs(Duration::MAX).unwrap(), u64::MAX);
+        assert!(ttl_secs(Duration::ZERO).is_err());
+    }
+}
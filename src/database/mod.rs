@@ -9,6 +9,8 @@
 //!
 //!
 
+/// Persistent collections on top of the key-value database.
+pub mod collections;
 /// key-value database module.
 pub mod kv;
 /// SQL database module.
@@ -0,0 +1,489 @@
+//! Build the SQL statements without string concatenation.
+//!
+//! The table and column names are quoted as identifiers, and the values are
+//! bound to `?` placeholders in order, so neither of them can inject SQL.
+//! Note that a quoted identifier is case-sensitive, e.g. `"ID"` doesn't match
+//! a column created as `ID` without quotes, which is folded into `id`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ws_sdk::database::sql::builder::*;
+//!
+//! insert_into("readings")
+//!     .columns(&["device_id", "value"])
+//!     .values(&[&"dev-1", &21.5])
+//!     .build()?
+//!     .execute()?;
+//!
+//! let query = select(&["device_id", "value"])
+//!     .from("readings")
+//!     .where_eq("device_id", &"dev-1")
+//!     .where_cmp("value", Cmp::Gt, &20.0)
+//!     .order_by("value", Order::Desc)
+//!     .limit(10)
+//!     .build()?;
+//! let rows = query.query_rows()?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+use super::{DBQuery, Param, SQLType};
+use anyhow::{bail, Result};
+
+/// The comparison operator of a `WHERE` condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    /// `=`
+    Eq,
+    /// `<>`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `LIKE`, whose pattern matches any characters with `%` and one with `_`.
+    Like,
+}
+
+impl Cmp {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            Cmp::Eq => "=",
+            Cmp::Ne => "<>",
+            Cmp::Lt => "<",
+            Cmp::Le => "<=",
+            Cmp::Gt => ">",
+            Cmp::Ge => ">=",
+            Cmp::Like => "LIKE",
+        }
+    }
+}
+
+/// The direction of an `ORDER BY` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// `ASC`, from the smallest value.
+    Asc,
+    /// `DESC`, from the largest value.
+    Desc,
+}
+
+/// Quotes a table or column name as an identifier.
+///
+/// A qualified name such as `schema.table` is quoted part by part, and `*`
+/// is kept as is.
+pub fn quote_ident(name: &str) -> Result<String> {
+    if name == "*" {
+        return Ok(name.to_string());
+    }
+    let mut quoted = Vec::new();
+    for part in name.split('.') {
+        if part.is_empty() || part.contains('\0') {
+            bail!("invalid identifier `{}`", name);
+        }
+        quoted.push(format!("\"{}\"", part.replace('"', "\"\"")));
+    }
+    Ok(quoted.join("."))
+}
+
+fn quote_idents(names: &[String]) -> Result<String> {
+    Ok(names
+        .iter()
+        .map(|n| quote_ident(n))
+        .collect::<Result<Vec<_>>>()?
+        .join(", "))
+}
+
+fn placeholders(n: usize) -> String {
+    vec!["?"; n].join(", ")
+}
+
+enum Condition {
+    Cmp(String, Cmp, Param),
+    Null(String, bool),
+    In(String, Vec<Param>),
+    Raw(String, Vec<Param>),
+}
+
+#[derive(Default)]
+struct Filter {
+    conditions: Vec<Condition>,
+}
+
+impl Filter {
+    fn write(&self, sql: &mut String, params: &mut Vec<Param>) -> Result<()> {
+        let mut clauses = Vec::with_capacity(self.conditions.len());
+        for cond in &self.conditions {
+            clauses.push(match cond {
+                Condition::Cmp(col, cmp, v) => {
+                    params.push(v.clone());
+                    format!("{} {} ?", quote_ident(col)?, cmp.as_sql())
+                }
+                Condition::Null(col, true) => format!("{} IS NULL", quote_ident(col)?),
+                Condition::Null(col, false) => format!("{} IS NOT NULL", quote_ident(col)?),
+                // `IN ()` is invalid SQL, and matches nothing anyway.
+                Condition::In(_, vs) if vs.is_empty() => "1 = 0".to_string(),
+                Condition::In(col, vs) => {
+                    params.extend(vs.iter().cloned());
+                    format!("{} IN ({})", quote_ident(col)?, placeholders(vs.len()))
+                }
+                Condition::Raw(expr, vs) => {
+                    params.extend(vs.iter().cloned());
+                    format!("({})", expr)
+                }
+            });
+        }
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+        Ok(())
+    }
+}
+
+fn to_params(values: &[&dyn SQLType]) -> Vec<Param> {
+    values.iter().map(|v| v.get_param()).collect()
+}
+
+macro_rules! impl_where {
+    ($builder:ty) => {
+        impl $builder {
+            /// Adds the condition `column = value`.
+            pub fn where_eq(self, column: &str, value: &dyn SQLType) -> Self {
+                self.where_cmp(column, Cmp::Eq, value)
+            }
+
+            /// Adds the condition `column <cmp> value`.
+            pub fn where_cmp(mut self, column: &str, cmp: Cmp, value: &dyn SQLType) -> Self {
+                self.filter.conditions.push(Condition::Cmp(
+                    column.to_string(),
+                    cmp,
+                    value.get_param(),
+                ));
+                self
+            }
+
+            /// Adds the condition `column IS NULL`.
+            pub fn where_null(mut self, column: &str) -> Self {
+                self.filter
+                    .conditions
+                    .push(Condition::Null(column.to_string(), true));
+                self
+            }
+
+            /// Adds the condition `column IS NOT NULL`.
+            pub fn where_not_null(mut self, column: &str) -> Self {
+                self.filter
+                    .conditions
+                    .push(Condition::Null(column.to_string(), false));
+                self
+            }
+
+            /// Adds the condition `column IN (values...)`.
+            pub fn where_in(mut self, column: &str, values: &[&dyn SQLType]) -> Self {
+                self.filter
+                    .conditions
+                    .push(Condition::In(column.to_string(), to_params(values)));
+                self
+            }
+
+            /// Adds a raw condition with its `?` placeholders bound to `params`.
+            ///
+            /// The expression is not quoted, so it must not be built from
+            /// untrusted input.
+            pub fn where_raw(mut self, expr: &str, params: &[&dyn SQLType]) -> Self {
+                self.filter
+                    .conditions
+                    .push(Condition::Raw(expr.to_string(), to_params(params)));
+                self
+            }
+        }
+    };
+}
+
+/// Starts an `INSERT` statement into `table`.
+pub fn insert_into(table: &str) -> Insert {
+    Insert {
+        table: table.to_string(),
+        columns: Vec::new(),
+        rows: Vec::new(),
+    }
+}
+
+/// An `INSERT` statement builder.
+pub struct Insert {
+    table: String,
+    columns: Vec<String>,
+    rows: Vec<Vec<Param>>,
+}
+
+impl Insert {
+    /// Sets the columns to insert.
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns = columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Adds a row of values, in the order of the columns.
+    ///
+    /// It can be called multiple times to insert multiple rows.
+    pub fn values(mut self, values: &[&dyn SQLType]) -> Self {
+        self.rows.push(to_params(values));
+        self
+    }
+
+    /// Builds the statement.
+    pub fn build(self) -> Result<DBQuery> {
+        if self.columns.is_empty() {
+            bail!("no column to insert into `{}`", self.table);
+        }
+        if self.rows.is_empty() {
+            bail!("no row to insert into `{}`", self.table);
+        }
+        let mut params = Vec::with_capacity(self.columns.len() * self.rows.len());
+        for row in self.rows {
+            if row.len() != self.columns.len() {
+                bail!(
+                    "the row has {} values, but there are {} columns",
+                    row.len(),
+                    self.columns.len()
+                );
+            }
+            params.extend(row);
+        }
+        let row = format!("({})", placeholders(self.columns.len()));
+        let statement = format!(
+            "INSERT INTO {} ({}) VALUES {};",
+            quote_ident(&self.table)?,
+            quote_idents(&self.columns)?,
+            vec![row; params.len() / self.columns.len()].join(", ")
+        );
+        Ok(DBQuery { statement, params })
+    }
+}
+
+/// Starts a `SELECT` statement of `columns`.
+pub fn select(columns: &[&str]) -> Select {
+    Select {
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+        table: String::new(),
+        filter: Filter::default(),
+        order: Vec::new(),
+        limit: None,
+        offset: None,
+    }
+}
+
+/// A `SELECT` statement builder.
+pub struct Select {
+    columns: Vec<String>,
+    table: String,
+    filter: Filter,
+    order: Vec<(String, Order)>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+impl_where!(Select);
+
+impl Select {
+    /// Sets the table to select from.
+    pub fn from(mut self, table: &str) -> Self {
+        self.table = table.to_string();
+        self
+    }
+
+    /// Adds a column to sort by.
+    pub fn order_by(mut self, column: &str, order: Order) -> Self {
+        self.order.push((column.to_string(), order));
+        self
+    }
+
+    /// Sets the maximum number of rows.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the number of rows to skip.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Builds the statement.
+    pub fn build(self) -> Result<DBQuery> {
+        if self.table.is_empty() {
+            bail!("no table to select from");
+        }
+        if self.columns.is_empty() {
+            bail!("no column to select from `{}`", self.table);
+        }
+        let mut params = Vec::new();
+        let mut statement = format!(
+            "SELECT {} FROM {}",
+            quote_idents(&self.columns)?,
+            quote_ident(&self.table)?
+        );
+        self.filter.write(&mut statement, &mut params)?;
+        if !self.order.is_empty() {
+            let order = self
+                .order
+                .iter()
+                .map(|(col, order)| {
+                    let dir = match order {
+                        Order::Asc => "ASC",
+                        Order::Desc => "DESC",
+                    };
+                    Ok(format!("{} {}", quote_ident(col)?, dir))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            statement.push_str(" ORDER BY ");
+            statement.push_str(&order.join(", "));
+        }
+        if let Some(limit) = self.limit {
+            statement.push_str(&format!(" LIMIT {}", limit));
+        }
+        if let Some(offset) = self.offset {
+            statement.push_str(&format!(" OFFSET {}", offset));
+        }
+        statement.push(';');
+        Ok(DBQuery { statement, params })
+    }
+}
+
+/// Starts an `UPDATE` statement of `table`.
+pub fn update(table: &str) -> Update {
+    Update {
+        table: table.to_string(),
+        sets: Vec::new(),
+        filter: Filter::default(),
+    }
+}
+
+/// An `UPDATE` statement builder.
+pub struct Update {
+    table: String,
+    sets: Vec<(String, Param)>,
+    filter: Filter,
+}
+
+impl_where!(Update);
+
+impl Update {
+    /// Sets a column to the value.
+    pub fn set(mut self, column: &str, value: &dyn SQLType) -> Self {
+        self.sets.push((column.to_string(), value.get_param()));
+        self
+    }
+
+    /// Builds the statement.
+    pub fn build(self) -> Result<DBQuery> {
+        if self.sets.is_empty() {
+            bail!("no column to update in `{}`", self.table);
+        }
+        let mut params = Vec::new();
+        let mut sets = Vec::with_capacity(self.sets.len());
+        for (col, v) in self.sets {
+            sets.push(format!("{} = ?", quote_ident(&col)?));
+            params.push(v);
+        }
+        let mut statement = format!(
+            "UPDATE {} SET {}",
+            quote_ident(&self.table)?,
+            sets.join(", ")
+        );
+        self.filter.write(&mut statement, &mut params)?;
+        statement.push(';');
+        Ok(DBQuery { statement, params })
+    }
+}
+
+/// Starts a `DELETE` statement from `table`.
+pub fn delete(table: &str) -> Delete {
+    Delete {
+        table: table.to_string(),
+        filter: Filter::default(),
+    }
+}
+
+/// A `DELETE` statement builder.
+pub struct Delete {
+    table: String,
+    filter: Filter,
+}
+
+impl_where!(Delete);
+
+impl Delete {
+    /// Builds the statement.
+    pub fn build(self) -> Result<DBQuery> {
+        let mut params = Vec::new();
+        let mut statement = format!("DELETE FROM {}", quote_ident(&self.table)?);
+        self.filter.write(&mut statement, &mut params)?;
+        statement.push(';');
+        Ok(DBQuery { statement, params })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let q = insert_into("readings")
+            .columns(&["device_id", "value"])
+            .values(&[&"dev-1", &1])
+            .values(&[&"dev-2", &2])
+            .build()
+            .unwrap();
+        assert_eq!(
+            q.statement,
+            r#"INSERT INTO "readings" ("device_id", "value") VALUES (?, ?), (?, ?);"#
+        );
+        assert_eq!(q.params, to_params(&[&"dev-1", &1, &"dev-2", &2]));
+
+        let q = select(&["*"])
+            .from("public.readings")
+            .where_eq("device_id", &"dev-1")
+            .where_in("kind", &[&1, &2])
+            .where_not_null("value")
+            .order_by("ts", Order::Desc)
+            .limit(10)
+            .build()
+            .unwrap();
+        assert_eq!(
+            q.statement,
+            r#"SELECT * FROM "public"."readings" WHERE "device_id" = ? AND "kind" IN (?, ?) AND "value" IS NOT NULL ORDER BY "ts" DESC LIMIT 10;"#
+        );
+        assert_eq!(q.params, to_params(&[&"dev-1", &1, &2]));
+
+        let q = update("devices")
+            .set("name", &"a\"b")
+            .where_cmp("id", Cmp::Ge, &3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            q.statement,
+            r#"UPDATE "devices" SET "name" = ? WHERE "id" >= ?;"#
+        );
+        assert_eq!(q.params, to_params(&[&"a\"b", &3]));
+
+        let q = delete("dev\"ices").where_null("name").build().unwrap();
+        assert_eq!(
+            q.statement,
+            r#"DELETE FROM "dev""ices" WHERE "name" IS NULL;"#
+        );
+
+        assert!(insert_into("t")
+            .columns(&["a"])
+            .values(&[])
+            .build()
+            .is_err());
+        assert!(select(&["a"]).from("t..x").build().is_err());
+    }
+}
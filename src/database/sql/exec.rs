@@ -0,0 +1,62 @@
+use crate::database::sql_types::*;
+use crate::host::abi::*;
+use anyhow::{bail, Result};
+
+/// Execute a prepared statement with parameters.
+///
+/// This func is often used to write records into the database.
+/// # Examples
+///
+/// ```no_run
+/// use ws-sdk::database::sql::execute
+/// execute("INSERT INTO table (ID, COUNT) VALUES (?, ?);", &[&0, &32])?;
+/// ```
+pub fn execute(prepared: &str, params: &[&dyn SQLType]) -> Result<()> {
+    DBQuery::new(prepared, params).execute()
+}
+
+/// Query a prepared statement with parameters.
+///
+/// This func is often used to read records from the database.
+/// The query result is returned in `json` format, see [`Rows`](super::Rows) for
+/// its layout and [`query_as`](super::query_as) for decoding it.
+/// # Examples
+///
+/// ```no_run
+/// use ws-sdk::database::sql::query
+/// let ret = query("SELECT * FROM table;", &[])?;
+/// ```
+pub fn query(prepared: &str, params: &[&dyn SQLType]) -> Result<Vec<u8>> {
+    DBQuery::new(prepared, params).query()
+}
+
+impl DBQuery {
+    /// Creates a query from a prepared statement with parameters.
+    pub fn new(prepared: &str, params: &[&dyn SQLType]) -> Self {
+        DBQuery {
+            statement: prepared.to_string(),
+            params: params.iter().map(|x| x.get_param()).collect(),
+        }
+    }
+
+    /// Execute the query, see [`execute`].
+    pub fn execute(&self) -> Result<()> {
+        let encoded = &self.encode()?;
+        match unsafe { ws_set_sql_db(encoded.as_ptr(), encoded.len() as _) } {
+            0 => Ok(()),
+            _ => bail!("fail to exec sql"),
+        }
+    }
+
+    /// Run the query and return the result in `json` format, see [`query`].
+    pub fn query(&self) -> Result<Vec<u8>> {
+        let encoded = &self.encode()?;
+        let data_ptr = &mut 0_i32 as *const _ as *const *mut u8;
+        let data_size = &mut 0_i32 as *const i32;
+
+        match unsafe { ws_get_sql_db(encoded.as_ptr(), encoded.len() as _, data_ptr, data_size) } {
+            0 => Ok(unsafe { Vec::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) }),
+            _ => bail!("fail to query sql"),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use super::builder::insert_into;
+use super::{execute, query_one, transaction};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+const MIGRATIONS_TABLE: &str = "ws_migrations";
+
+/// A version of the schema, with the statements upgrading the schema from
+/// the previous version.
+pub struct Migration<'a> {
+    /// The version, which should be positive and increasing in the migration list.
+    pub version: u32,
+    /// The statements applied in order, without parameters.
+    pub steps: &'a [&'a str],
+}
+
+#[derive(Deserialize)]
+struct CurrentVersion {
+    version: Option<i64>,
+}
+
+/// Upgrades the schema by applying the pending migrations in order.
+///
+/// The applied versions are recorded in the `ws_migrations` table, which is
+/// created on the first run, so only the migrations newer than the current
+/// version are applied. It refuses to run if the current version is newer than
+/// the latest migration, i.e. the database is used by a newer module.
+/// Each migration is applied in a transaction together with its record.
+///
+/// Returns the current version after the upgrade.
+/// It's supposed to be called from one handler, e.g. at the beginning of `start`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::{migrate, Migration};
+///
+/// const MIGRATIONS: &[Migration] = &[
+///     Migration {
+///         version: 1,
+///         steps: &["CREATE TABLE readings (device_id TEXT NOT NULL, value DOUBLE PRECISION);"],
+///     },
+///     Migration {
+///         version: 2,
+///         steps: &["ALTER TABLE readings ADD COLUMN ts TIMESTAMPTZ;"],
+///     },
+/// ];
+///
+/// migrate(MIGRATIONS)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn migrate(migrations: &[Migration]) -> Result<u32> {
+    execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {} (version BIGINT PRIMARY KEY);",
+            MIGRATIONS_TABLE
+        ),
+        &[],
+    )?;
+    let current: CurrentVersion = query_one(
+        &format!("SELECT MAX(version) AS version FROM {};", MIGRATIONS_TABLE),
+        &[],
+    )?;
+    let current = schema_version(current.version)?;
+
+    let mut version = current;
+    for m in pending(migrations, current)? {
+        transaction(|tx| {
+            for (i, step) in m.steps.iter().enumerate() {
+                tx.execute(step, &[]).with_context(|| {
+                    format!("fail to apply step {} of migration {}", i, m.version)
+                })?;
+            }
+            tx.execute_query(
+                &insert_into(MIGRATIONS_TABLE)
+                    .columns(&["version"])
+                    .values(&[&(m.version as i64)])
+                    .build()?,
+            )
+        })?;
+        version = m.version;
+    }
+    Ok(version)
+}
+
+fn schema_version(version: Option<i64>) -> Result<u32> {
+    let version = version.unwrap_or(0);
+    match u32::try_from(version) {
+        Ok(version) => Ok(version),
+        Err(_) => bail!("fail to read the schema version {}", version),
+    }
+}
+
+fn pending<'a, 'b>(migrations: &'a [Migration<'b>], current: u32) -> Result<&'a [Migration<'b>]> {
+    let mut latest = 0;
+    for m in migrations {
+        if m.version <= latest {
+            bail!(
+                "the migration versions should be positive and increasing, found {} after {}",
+                m.version,
+                latest
+            );
+        }
+        latest = m.version;
+    }
+    if current > latest {
+        bail!(
+            "the schema version {} is newer than the latest known migration {}",
+            current,
+            latest
+        );
+    }
+    let applied = migrations
+        .iter()
+        .take_while(|m| m.version <= current)
+        .count();
+    Ok(&migrations[applied..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending() {
+        let migrations = [
+            Migration {
+                version: 1,
+                steps: &["CREATE TABLE a (id INT);"],
+            },
+            Migration {
+                version: 3,
+                steps: &["CREATE TABLE b (id INT);"],
+            },
+        ];
+        assert_eq!(pending(&migrations, 0).unwrap().len(), 2);
+        assert_eq!(pending(&migrations, 1).unwrap()[0].version, 3);
+        assert!(pending(&migrations, 3).unwrap().is_empty());
+        assert!(pending(&migrations, 4).is_err());
+
+        let unordered = [
+            Migration {
+                version: 2,
+                steps: &[],
+            },
+            Migration {
+                version: 1,
+                steps: &[],
+            },
+        ];
+        assert!(pending(&unordered, 0).is_err());
+    }
+
+    #[test]
+    fn test_schema_version() {
+        assert_eq!(schema_version(None).unwrap(), 0);
+        assert_eq!(schema_version(Some(3)).unwrap(), 3);
+        assert!(schema_version(Some(-1)).is_err());
+        assert!(schema_version(Some(u32::MAX as i64 + 1)).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+mod exec;
+pub use self::exec::*;
+mod named;
+pub use self::named::*;
+mod rows;
+pub use self::rows::*;
+pub use crate::database::sql_types::{DBQuery, Decimal, Param, SQLType, Timestamp};
+/// SQL statement builder module.
+pub mod builder;
+mod sql_row;
+pub use self::sql_row::*;
+#[cfg(feature = "derive")]
+pub use ws_sdk_macros::SqlRow;
+mod migrate;
+pub use self::migrate::*;
+mod transaction;
+pub use self::transaction::*;
+mod stream;
+pub use self::stream::*;
@@ -0,0 +1,301 @@
+use super::{DBQuery, SQLType};
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+/// A placeholder found in the statement, with its byte range.
+struct Placeholder<'a> {
+    start: usize,
+    end: usize,
+    // `None` for the positional `?`
+    name: Option<&'a str>,
+}
+
+fn is_ident_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_'
+}
+
+fn is_ident_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+// Finds the placeholders outside the string literals, quoted identifiers and comments.
+fn placeholders(sql: &str) -> Vec<Placeholder<'_>> {
+    let b = sql.as_bytes();
+    let mut found = Vec::new();
+    // the depth of the array subscripts, where `lo:hi` is a slice
+    let mut brackets = 0usize;
+    let mut i = 0;
+    while i < b.len() {
+        match b[i] {
+            b'\'' => {
+                let escapes = i > 0
+                    && (b[i - 1] == b'E' || b[i - 1] == b'e')
+                    && (i < 2 || !is_ident_char(b[i - 2]));
+                i += 1;
+                while i < b.len() {
+                    if escapes && b[i] == b'\\' {
+                        i += 2;
+                        continue;
+                    }
+                    if b[i] == b'\'' {
+                        // `''` is an escaped quote
+                        if b.get(i + 1) == Some(&b'\'') {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'"' => {
+                i += 1;
+                while i < b.len() && b[i] != b'"' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' if b.get(i + 1) == Some(&b'-') => {
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if b.get(i + 1) == Some(&b'*') => {
+                i = match sql[i + 2..].find("*/") {
+                    Some(p) => i + 2 + p + 2,
+                    None => b.len(),
+                };
+            }
+            b'$' => {
+                // dollar-quoted string, e.g. `$$...$$` or `$tag$...$tag$`
+                let tag_len = b[i + 1..].iter().take_while(|c| is_ident_char(**c)).count();
+                let tag_end = i + 1 + tag_len;
+                let valid_tag = tag_len == 0 || is_ident_start(b[i + 1]);
+                if valid_tag && b.get(tag_end) == Some(&b'$') {
+                    let tag = &sql[i..=tag_end];
+                    i = match sql[tag_end + 1..].find(tag) {
+                        Some(p) => tag_end + 1 + p + tag.len(),
+                        None => b.len(),
+                    };
+                } else {
+                    i += 1;
+                }
+            }
+            b'?' => {
+                found.push(Placeholder {
+                    start: i,
+                    end: i + 1,
+                    name: None,
+                });
+                i += 1;
+            }
+            b'[' => {
+                brackets += 1;
+                i += 1;
+            }
+            b']' => {
+                brackets = brackets.saturating_sub(1);
+                i += 1;
+            }
+            b':' if b.get(i + 1) == Some(&b':') => {
+                // type cast, e.g. `value::text`
+                i += 2;
+            }
+            b':' if brackets > 0 && i > 0 && is_ident_char(b[i - 1]) => {
+                // array slice, e.g. `readings[lo:hi]`
+                i += 1;
+            }
+            b':' if matches!(b.get(i + 1), Some(c) if is_ident_start(*c)) => {
+                let len = b[i + 1..].iter().take_while(|c| is_ident_char(**c)).count();
+                found.push(Placeholder {
+                    start: i,
+                    end: i + 1 + len,
+                    name: Some(&sql[i + 1..i + 1 + len]),
+                });
+                i += 1 + len;
+            }
+            _ => i += 1,
+        }
+    }
+    found
+}
+
+impl DBQuery {
+    /// Creates a query from a statement with named parameters, e.g. `:device_id`.
+    ///
+    /// The named parameters are rewritten into the positional `?` placeholders,
+    /// and a name used multiple times is bound multiple times. The names of
+    /// `params` may be given with or without the leading `:`. It fails if a
+    /// parameter in the statement has no value, or a value is not used. The `:`
+    /// after a bound of an array slice, e.g. `readings[lo:hi]`, isn't taken as a
+    /// parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ws_sdk::database::sql::DBQuery;
+    /// let query = DBQuery::named(
+    ///     "SELECT * FROM readings WHERE device_id = :device_id AND ts > :since;",
+    ///     &[("device_id", &"dev-1"), ("since", &1680000000)],
+    /// )?;
+    /// assert_eq!(query.statement, "SELECT * FROM readings WHERE device_id = ? AND ts > ?;");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn named(prepared: &str, params: &[(&str, &dyn SQLType)]) -> Result<Self> {
+        let lookup = |name: &str| {
+            params
+                .iter()
+                .find(|(n, _)| n.strip_prefix(':').unwrap_or(n) == name)
+                .map(|(_, v)| *v)
+        };
+        let mut statement = String::with_capacity(prepared.len());
+        let mut bound = Vec::new();
+        let mut used = HashSet::new();
+        let mut last = 0;
+        for p in placeholders(prepared) {
+            let name = match p.name {
+                Some(name) => name,
+                None => bail!("positional `?` can't be mixed with named parameters"),
+            };
+            let value = match lookup(name) {
+                Some(v) => v,
+                None => bail!("no value for the named parameter `:{}`", name),
+            };
+            statement.push_str(&prepared[last..p.start]);
+            statement.push('?');
+            bound.push(value.get_param());
+            used.insert(name);
+            last = p.end;
+        }
+        statement.push_str(&prepared[last..]);
+        for (name, _) in params {
+            let name = name.strip_prefix(':').unwrap_or(name);
+            if !used.contains(name) {
+                bail!("the parameter `:{}` is not used in the statement", name);
+            }
+        }
+        Ok(DBQuery {
+            statement,
+            params: bound,
+        })
+    }
+
+    /// Checks that the number of the `?` placeholders matches the parameters.
+    ///
+    /// It's done before sending the query to the host.
+    pub fn check(&self) -> Result<()> {
+        let mut count = 0;
+        for p in placeholders(&self.statement) {
+            match p.name {
+                None => count += 1,
+                Some(name) => bail!(
+                    "the named parameter `:{}` isn't bound, use `DBQuery::named` for named parameters",
+                    name
+                ),
+            }
+        }
+        if count != self.params.len() {
+            bail!(
+                "the statement has {} placeholders, but {} parameters are given",
+                count,
+                self.params.len()
+            );
+        }
+        Ok(())
+    }
+
+    pub(super) fn encode(&self) -> Result<String> {
+        self.check()?;
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Execute a prepared statement with named parameters, e.g. `:device_id`.
+///
+/// See [`DBQuery::named`] for the binding rules.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::execute_named;
+/// execute_named(
+///     "INSERT INTO readings (device_id, value) VALUES (:device_id, :value);",
+///     &[("device_id", &"dev-1"), ("value", &21.5)],
+/// )?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn execute_named(prepared: &str, params: &[(&str, &dyn SQLType)]) -> Result<()> {
+    DBQuery::named(prepared, params)?.execute()
+}
+
+/// Query a prepared statement with named parameters, e.g. `:device_id`.
+///
+/// See [`DBQuery::named`] for the binding rules.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::query_named;
+/// let ret = query_named("SELECT * FROM readings WHERE device_id = :id;", &[("id", &"dev-1")])?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn query_named(prepared: &str, params: &[(&str, &dyn SQLType)]) -> Result<Vec<u8>> {
+    DBQuery::named(prepared, params)?.query()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named() {
+        let q = DBQuery::named(
+            "UPDATE t SET a = :a, b = ':b', c = $$:c$$, d = :a::text -- :e\nWHERE \"?\" = :f /* :g */;",
+            &[(":a", &1), ("f", &2)],
+        )
+        .unwrap();
+        assert_eq!(
+            q.statement,
+            "UPDATE t SET a = ?, b = ':b', c = $$:c$$, d = ?::text -- :e\nWHERE \"?\" = ? /* :g */;"
+        );
+        assert_eq!(q.params, vec![1.get_param(), 1.get_param(), 2.get_param()]);
+        assert!(q.check().is_ok());
+
+        let err = DBQuery::named("SELECT :a, :b;", &[("a", &1)]).unwrap_err();
+        assert_eq!(err.to_string(), "no value for the named parameter `:b`");
+        let err = DBQuery::named("SELECT :a;", &[("a", &1), ("c", &2)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the parameter `:c` is not used in the statement"
+        );
+        assert!(DBQuery::named("SELECT :a, ?;", &[("a", &1)]).is_err());
+    }
+
+    #[test]
+    fn test_named_array_slice() {
+        let q = DBQuery::named(
+            "SELECT readings[lo:hi], readings[1:n], readings[:a:hi] FROM t WHERE tags = ARRAY[:a, :b];",
+            &[("a", &1), ("b", &2)],
+        )
+        .unwrap();
+        assert_eq!(
+            q.statement,
+            "SELECT readings[lo:hi], readings[1:n], readings[?:hi] FROM t WHERE tags = ARRAY[?, ?];"
+        );
+        assert_eq!(q.params, vec![1.get_param(), 1.get_param(), 2.get_param()]);
+        assert!(DBQuery::new("SELECT readings[lo:hi] FROM t;", &[])
+            .check()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(DBQuery::new("SELECT ?, 'it''s ?', E'\\' ?';", &[&1])
+            .check()
+            .is_ok());
+        assert!(DBQuery::new("SELECT ?, ?;", &[&1]).check().is_err());
+        assert!(DBQuery::new("SELECT :a;", &[&1]).check().is_err());
+        assert!(DBQuery::new("SELECT 1;", &[]).check().is_ok());
+    }
+}
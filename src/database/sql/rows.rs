@@ -0,0 +1,613 @@
+use crate::database::sql_types::*;
+use anyhow::{bail, Context, Result};
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine as _,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::rc::Rc;
+
+const BYTES_ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+#[derive(Deserialize)]
+struct RawRows {
+    columns: Vec<String>,
+    #[serde(default)]
+    rows: Vec<Vec<Option<Param>>>,
+}
+
+/// A value of a column in a row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `NULL`.
+    Null,
+    /// A signed integer, e.g. of an `INT` or `BIGINT` column.
+    Int(i64),
+    /// An unsigned integer, e.g. of a `BIGINT UNSIGNED` column.
+    UInt(u64),
+    /// A floating point number, e.g. of a `REAL` or `DOUBLE` column.
+    Float(f64),
+    /// A text, e.g. of a `TEXT` or `VARCHAR` column.
+    Text(String),
+    /// A boolean.
+    Bool(bool),
+    /// The bytes of a binary column, e.g. `BYTEA`.
+    Bytes(Vec<u8>),
+    /// A date time, e.g. of a `TIMESTAMP` column.
+    Time(Timestamp),
+    /// A decimal of a `NUMERIC` or `DECIMAL` column.
+    Decimal(Decimal),
+    /// A `JSON` document.
+    Json(serde_json::Value),
+}
+
+impl Value {
+    fn from_param(param: Option<Param>) -> Result<Self> {
+        let p = match param {
+            Some(p) => p,
+            None => return Ok(Value::Null),
+        };
+        Ok(if p.null == Some(true) {
+            Value::Null
+        } else if let Some(v) = p.int32 {
+            Value::Int(v as _)
+        } else if let Some(v) = p.int64 {
+            Value::Int(v)
+        } else if let Some(v) = p.uint64 {
+            Value::UInt(v)
+        } else if let Some(v) = p.float32 {
+            Value::Float(v as _)
+        } else if let Some(v) = p.float64 {
+            Value::Float(v)
+        } else if let Some(v) = p.string {
+            Value::Text(v)
+        } else if let Some(v) = p.bool {
+            Value::Bool(v)
+        } else if let Some(v) = p.bytes {
+            Value::Bytes(
+                BYTES_ENGINE
+                    .decode(v)
+                    .context("fail to decode the bytes column")?,
+            )
+        } else if let Some(v) = p.time {
+            Value::Time(v.parse()?)
+        } else if let Some(v) = p.decimal {
+            Value::Decimal(v.parse()?)
+        } else if let Some(v) = p.json {
+            Value::Json(serde_json::from_str(&v).context("fail to decode the json column")?)
+        } else {
+            Value::Null
+        })
+    }
+
+    /// Returns `true` if the value is `NULL`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the value if it's an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(v) => Some(*v),
+            Value::UInt(v) => i64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's a non-negative integer.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Int(v) => u64::try_from(*v).ok(),
+            Value::UInt(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(v) => Some(*v),
+            Value::Int(v) => Some(*v as _),
+            Value::UInt(v) => Some(*v as _),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's a text.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Text(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's bytes.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's a point in time.
+    pub fn as_time(&self) -> Option<Timestamp> {
+        match self {
+            Value::Time(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's a decimal.
+    pub fn as_decimal(&self) -> Option<&Decimal> {
+        match self {
+            Value::Decimal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's a json document.
+    pub fn as_json(&self) -> Option<&serde_json::Value> {
+        match self {
+            Value::Json(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Value::Null => serde_json::Value::Null,
+            Value::Int(v) => (*v).into(),
+            Value::UInt(v) => (*v).into(),
+            Value::Float(v) => (*v).into(),
+            Value::Text(v) => v.as_str().into(),
+            Value::Bool(v) => (*v).into(),
+            Value::Bytes(v) => v.as_slice().into(),
+            Value::Time(v) => v.to_string().into(),
+            Value::Decimal(v) => v.as_str().into(),
+            Value::Json(v) => v.clone(),
+        }
+    }
+}
+
+impl SQLType for Value {
+    fn get_param(&self) -> Param {
+        match self {
+            Value::Null => None::<i64>.get_param(),
+            Value::Int(v) => v.get_param(),
+            Value::UInt(v) => v.get_param(),
+            Value::Float(v) => v.get_param(),
+            Value::Text(v) => v.get_param(),
+            Value::Bool(v) => v.get_param(),
+            Value::Bytes(v) => v.get_param(),
+            Value::Time(v) => v.get_param(),
+            Value::Decimal(v) => v.get_param(),
+            Value::Json(v) => v.get_param(),
+        }
+    }
+}
+
+/// A type which can be converted from a [`Value`] of the query result.
+pub trait FromValue: Sized {
+    /// Converts the value, or returns an error if it's of another type.
+    fn from_value(value: &Value) -> Result<Self>;
+}
+
+fn mismatch<T>(expected: &str, value: &Value) -> Result<T> {
+    bail!("expected {}, found {:?}", expected, value)
+}
+
+macro_rules! impl_from_value_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromValue for $t {
+                fn from_value(value: &Value) -> Result<Self> {
+                    let v = match value {
+                        Value::Int(v) => <$t>::try_from(*v).ok(),
+                        Value::UInt(v) => <$t>::try_from(*v).ok(),
+                        Value::Decimal(v) => v.as_str().parse().ok(),
+                        _ => return mismatch("an integer", value),
+                    };
+                    v.with_context(|| format!("{:?} is out of the range of `{}`", value, stringify!($t)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_for_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value.as_f64() {
+            Some(v) => Ok(v),
+            None => mismatch("a number", value),
+        }
+    }
+}
+
+impl FromValue for f32 {
+    fn from_value(value: &Value) -> Result<Self> {
+        Ok(f64::from_value(value)? as _)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Bool(v) => Ok(*v),
+            _ => mismatch("a boolean", value),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Text(v) => Ok(v.clone()),
+            _ => mismatch("a text", value),
+        }
+    }
+}
+
+impl FromValue for Vec<u8> {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Bytes(v) => Ok(v.clone()),
+            _ => mismatch("bytes", value),
+        }
+    }
+}
+
+impl FromValue for Timestamp {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Time(v) => Ok(*v),
+            Value::Text(v) => v.parse(),
+            _ => mismatch("a time", value),
+        }
+    }
+}
+
+impl FromValue for std::time::SystemTime {
+    fn from_value(value: &Value) -> Result<Self> {
+        Ok(Timestamp::from_value(value)?.into())
+    }
+}
+
+impl FromValue for Decimal {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Decimal(v) => Ok(v.clone()),
+            Value::Int(v) => Ok((*v).into()),
+            Value::UInt(v) => Ok((*v).into()),
+            Value::Text(v) => v.parse(),
+            _ => mismatch("a decimal", value),
+        }
+    }
+}
+
+impl FromValue for serde_json::Value {
+    fn from_value(value: &Value) -> Result<Self> {
+        Ok(value.to_json())
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Ok(None),
+            _ => T::from_value(value).map(Some),
+        }
+    }
+}
+
+/// A row of the query result.
+#[derive(Debug, Clone)]
+pub struct Row {
+    columns: Rc<[String]>,
+    values: Vec<Value>,
+}
+
+impl Row {
+    /// Retrieves the value of the column by its name.
+    pub fn get(&self, column: &str) -> Option<&Value> {
+        self.columns
+            .iter()
+            .position(|c| c == column)
+            .map(|i| &self.values[i])
+    }
+
+    /// Retrieves the value of the column by its name and converts it into `T`.
+    pub fn get_as<T: FromValue>(&self, column: &str) -> Result<T> {
+        match self.get(column) {
+            Some(v) => {
+                T::from_value(v).with_context(|| format!("fail to decode column `{}`", column))
+            }
+            None => bail!("no column `{}` in the row", column),
+        }
+    }
+
+    /// Returns the column names of the row.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the values of the row in the column order.
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    /// Decodes the row into `T`, whose fields are matched with the columns by name.
+    ///
+    /// `NULL` is decoded as `None` of an `Option` field; a bytes column is
+    /// decoded into a `Vec<u8>` field; time and decimal columns are decoded into
+    /// [`Timestamp`] and [`Decimal`] fields; a json column is decoded as a
+    /// nested value.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T> {
+        let obj: serde_json::Map<String, serde_json::Value> = self
+            .columns
+            .iter()
+            .cloned()
+            .zip(self.values.iter().map(Value::to_json))
+            .collect();
+        serde_json::from_value(obj.into()).context("fail to decode the row")
+    }
+}
+
+/// The result set of a query.
+///
+/// The host returns the query result in `json` format, holding the column names
+/// and the rows. Each value in a row is encoded the same way as a parameter of
+/// the statement, e.g. `{"int64": 1}`, and `null` stands for `NULL`:
+///
+/// ```json
+/// {
+///     "columns": ["id", "name"],
+///     "rows": [[{"int64": 1}, {"string": "dev-1"}], [{"int64": 2}, null]]
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rows {
+    columns: Rc<[String]>,
+    rows: Vec<Row>,
+}
+
+impl Rows {
+    /// Decodes the query result returned by the host.
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        let raw: RawRows =
+            serde_json::from_slice(data).context("fail to decode the query result")?;
+        let columns: Rc<[String]> = raw.columns.into();
+        let rows = raw
+            .rows
+            .into_iter()
+            .map(|r| {
+                if r.len() != columns.len() {
+                    bail!(
+                        "the row has {} values, but there are {} columns",
+                        r.len(),
+                        columns.len()
+                    );
+                }
+                Ok(Row {
+                    columns: columns.clone(),
+                    values: r
+                        .into_iter()
+                        .map(Value::from_param)
+                        .collect::<Result<_>>()?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Rows { columns, rows })
+    }
+
+    /// Returns the column names.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if there is no row.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Iterates over the rows.
+    pub fn iter(&self) -> std::slice::Iter<'_, Row> {
+        self.rows.iter()
+    }
+
+    /// Decodes every row into `T`.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<Vec<T>> {
+        self.rows.iter().map(Row::decode).collect()
+    }
+}
+
+impl IntoIterator for Rows {
+    type Item = Row;
+    type IntoIter = std::vec::IntoIter<Row>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+/// Query a prepared statement with parameters and decode the result into [`Rows`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::query_rows;
+/// let rows = query_rows("SELECT id, name FROM devices;", &[])?;
+/// for row in rows.iter() {
+///     let id = row.get("id").and_then(|v| v.as_i64());
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn query_rows(prepared: &str, params: &[&dyn SQLType]) -> Result<Rows> {
+    DBQuery::new(prepared, params).query_rows()
+}
+
+/// Query a prepared statement with parameters and decode each row into `T`.
+///
+/// The fields of `T` are matched with the columns by name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use serde::Deserialize;
+/// use ws_sdk::database::sql::query_as;
+///
+/// #[derive(Deserialize)]
+/// struct Device {
+///     id: i64,
+///     name: Option<String>,
+/// }
+///
+/// let devices: Vec<Device> = query_as("SELECT id, name FROM devices;", &[])?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn query_as<T: DeserializeOwned>(prepared: &str, params: &[&dyn SQLType]) -> Result<Vec<T>> {
+    DBQuery::new(prepared, params).query_as()
+}
+
+/// Query a prepared statement which returns exactly one row and decode it into `T`.
+///
+/// It fails if there is no row or more than one row.
+///
+/// # Examples
+///
+/// ```no_run
+/// use serde::Deserialize;
+/// use ws_sdk::database::sql::query_one;
+///
+/// #[derive(Deserialize)]
+/// struct Count {
+///     count: i64,
+/// }
+///
+/// let total: Count = query_one("SELECT COUNT(*) AS count FROM devices;", &[])?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn query_one<T: DeserializeOwned>(prepared: &str, params: &[&dyn SQLType]) -> Result<T> {
+    DBQuery::new(prepared, params).query_one()
+}
+
+/// Query a prepared statement which returns at most one row and decode it into `T`.
+///
+/// It fails if there is more than one row.
+///
+/// # Examples
+///
+/// ```no_run
+/// use serde::Deserialize;
+/// use ws_sdk::database::sql::query_opt;
+///
+/// #[derive(Deserialize)]
+/// struct Device {
+///     id: i64,
+/// }
+///
+/// let device: Option<Device> = query_opt("SELECT id FROM devices WHERE id = ?;", &[&1])?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn query_opt<T: DeserializeOwned>(
+    prepared: &str,
+    params: &[&dyn SQLType],
+) -> Result<Option<T>> {
+    DBQuery::new(prepared, params).query_opt()
+}
+
+impl DBQuery {
+    /// Run the query and decode the result into [`Rows`], see [`query_rows`].
+    pub fn query_rows(&self) -> Result<Rows> {
+        Rows::from_slice(&self.query()?)
+    }
+
+    /// Run the query and decode each row into `T`, see [`query_as`].
+    pub fn query_as<T: DeserializeOwned>(&self) -> Result<Vec<T>> {
+        self.query_rows()?.decode()
+    }
+
+    /// Run the query which returns exactly one row, see [`query_one`].
+    pub fn query_one<T: DeserializeOwned>(&self) -> Result<T> {
+        match self.query_opt()? {
+            Some(v) => Ok(v),
+            None => bail!("the query returns no row"),
+        }
+    }
+
+    /// Run the query which returns at most one row, see [`query_opt`].
+    pub fn query_opt<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        let rows = self.query_rows()?;
+        match rows.len() {
+            0 => Ok(None),
+            1 => Ok(Some(rows.rows[0].decode()?)),
+            n => bail!("the query returns {} rows, expected at most one", n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_rows() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Device {
+            id: i64,
+            name: Option<String>,
+            key: Vec<u8>,
+            balance: Option<Decimal>,
+            seen: Timestamp,
+        }
+
+        let data = br#"{
+            "columns": ["id", "name", "key", "balance", "seen"],
+            "rows": [
+                [{"int64": 1}, {"string": "dev-1"}, {"bytes": "AQI"},
+                    {"decimal": "1000000000000000000000"}, {"time": "2023-04-01T00:00:00Z"}],
+                [{"int32": 2}, null, {"bytes": "AQI="}, {"null": true}, {"time": "1970-01-01T00:00:00Z"}]
+            ]
+        }"#;
+        let rows = Rows::from_slice(data).unwrap();
+        assert_eq!(rows.columns(), ["id", "name", "key", "balance", "seen"]);
+        assert_eq!(rows.iter().nth(1).unwrap().get("name"), Some(&Value::Null));
+        assert_eq!(
+            rows.decode::<Device>().unwrap(),
+            vec![
+                Device {
+                    id: 1,
+                    name: Some("dev-1".to_string()),
+                    key: vec![1, 2],
+                    balance: Some(Decimal::from(1_000_000_000_000_000_000_000u128)),
+                    seen: Timestamp::from_unix(1680307200),
+                },
+                Device {
+                    id: 2,
+                    name: None,
+                    key: vec![1, 2],
+                    balance: None,
+                    seen: Timestamp::from_unix(0),
+                },
+            ]
+        );
+    }
+}
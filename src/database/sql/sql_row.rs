@@ -0,0 +1,70 @@
+use super::builder::{insert_into, select, Select};
+use super::{DBQuery, Row, Rows, SQLType};
+use anyhow::Result;
+
+/// A struct mapped to a row of a SQL table.
+///
+/// It's usually derived with `#[derive(SqlRow)]` by enabling the `derive`
+/// feature flag. Each field is a column, which is bound by its [`SQLType`]
+/// and decoded by its [`FromValue`](super::FromValue); an `Option` field is
+/// a nullable column. The derive macro supports the following attributes:
+///
+///  * `#[sql(table = "name")]` on the struct sets the table name, which is the
+///    struct name in snake case by default.
+///  * `#[sql(rename = "name")]` on a field sets the column name, which is the
+///    field name by default.
+///  * `#[sql(skip)]` on a field excludes it from the table. It's set to
+///    `Default::default()` when decoding.
+///
+/// # Examples
+///
+/// ```ignore
+/// use ws_sdk::database::sql::SqlRow;
+///
+/// #[derive(SqlRow)]
+/// #[sql(table = "readings")]
+/// struct Reading {
+///     #[sql(rename = "device_id")]
+///     device: String,
+///     value: f64,
+///     note: Option<String>,
+///     #[sql(skip)]
+///     cached: bool,
+/// }
+///
+/// let reading = Reading { device: "dev-1".into(), value: 21.5, note: None, cached: false };
+/// reading.insert()?.execute()?;
+///
+/// let query = Reading::select().where_eq("device_id", &"dev-1").build()?;
+/// let readings = Reading::from_rows(&query.query_rows()?)?;
+/// ```
+pub trait SqlRow: Sized {
+    /// The table name.
+    const TABLE: &'static str;
+    /// The column names, in the order of [`SqlRow::sql_values`].
+    const COLUMNS: &'static [&'static str];
+
+    /// Returns the values of the columns.
+    fn sql_values(&self) -> Vec<&dyn SQLType>;
+
+    /// Decodes a row of the query result.
+    fn from_row(row: &Row) -> Result<Self>;
+
+    /// Decodes all the rows of the query result.
+    fn from_rows(rows: &Rows) -> Result<Vec<Self>> {
+        rows.iter().map(Self::from_row).collect()
+    }
+
+    /// Builds the `INSERT` statement of the row.
+    fn insert(&self) -> Result<DBQuery> {
+        insert_into(Self::TABLE)
+            .columns(Self::COLUMNS)
+            .values(&self.sql_values())
+            .build()
+    }
+
+    /// Starts a `SELECT` statement of all the columns from the table.
+    fn select() -> Select {
+        select(Self::COLUMNS).from(Self::TABLE)
+    }
+}
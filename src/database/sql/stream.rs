@@ -0,0 +1,322 @@
+use super::builder::{quote_ident, Order};
+use super::{DBQuery, Param, Row, SQLType};
+#[cfg(feature = "host-sql-cursor")]
+use crate::host::abi::*;
+use anyhow::{bail, Result};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+enum Source {
+    Offset {
+        offset: u64,
+    },
+    Keyset {
+        column: String,
+        order: Order,
+        last: Option<Box<Param>>,
+    },
+    #[cfg(feature = "host-sql-cursor")]
+    Cursor {
+        id: Option<i32>,
+    },
+}
+
+impl Source {
+    fn page_query(&self, query: &DBQuery, page_size: u32) -> Result<DBQuery> {
+        let mut params = query.params.clone();
+        let statement = match self {
+            Source::Offset { offset } => {
+                params.push((page_size as i64).get_param());
+                params.push((*offset as i64).get_param());
+                format!("{} LIMIT ? OFFSET ?", query.statement)
+            }
+            Source::Keyset {
+                column,
+                order,
+                last,
+            } => {
+                let column = quote_ident(column)?;
+                let (cmp, dir) = match order {
+                    Order::Asc => (">", "ASC"),
+                    Order::Desc => ("<", "DESC"),
+                };
+                let mut sql = format!("SELECT * FROM ({}) AS ws_page", query.statement);
+                if let Some(last) = last {
+                    params.push(Param::clone(last));
+                    sql.push_str(&format!(" WHERE {} {} ?", column, cmp));
+                }
+                params.push((page_size as i64).get_param());
+                sql.push_str(&format!(" ORDER BY {} {} LIMIT ?", column, dir));
+                sql
+            }
+            #[cfg(feature = "host-sql-cursor")]
+            Source::Cursor { .. } => return Ok(query.clone()),
+        };
+        Ok(DBQuery { statement, params })
+    }
+}
+
+/// An iterator over the rows of a query, fetching them page by page.
+///
+/// Only one page of rows is held in memory at a time, so a large table can be
+/// processed in bounded memory. The pages are fetched in one of the ways:
+///
+///  * [`QueryStream::offset`] appends `LIMIT ? OFFSET ?` to the statement,
+///    which should have an `ORDER BY` for a stable order and no `LIMIT` of its own.
+///  * [`QueryStream::keyset`] wraps the statement and continues after the key
+///    of the last row, which is faster for the deep pages. The key column should
+///    be unique, non-null and selected by the statement.
+///  * [`QueryStream::cursor`] reads from a cursor on the host, which requires
+///    the `host-sql-cursor` feature.
+///
+/// [`query_stream`] picks the host cursor if available, and the offset otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use serde::Deserialize;
+/// use ws_sdk::database::sql::{builder::Order, DBQuery, QueryStream};
+///
+/// #[derive(Deserialize)]
+/// struct Reading {
+///     id: i64,
+///     value: f64,
+/// }
+///
+/// let query = DBQuery::new("SELECT id, value FROM readings WHERE device_id = ?", &[&"dev-1"]);
+/// for reading in QueryStream::keyset(query, "id", Order::Asc).page_size(500).decode::<Reading>() {
+///     let reading = reading?;
+///     // ...
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct QueryStream {
+    query: DBQuery,
+    source: Source,
+    page_size: u32,
+    buffer: VecDeque<Row>,
+    done: bool,
+}
+
+impl QueryStream {
+    fn with_source(mut query: DBQuery, source: Source) -> Self {
+        let trimmed = query
+            .statement
+            .trim_end_matches(|c: char| c == ';' || c.is_whitespace());
+        query.statement.truncate(trimmed.len());
+        QueryStream {
+            query,
+            source,
+            page_size: DEFAULT_PAGE_SIZE,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Streams the query with `LIMIT`/`OFFSET` pagination.
+    pub fn offset(query: DBQuery) -> Self {
+        Self::with_source(query, Source::Offset { offset: 0 })
+    }
+
+    /// Streams the query with keyset pagination on `column` in the given order.
+    pub fn keyset(query: DBQuery, column: &str, order: Order) -> Self {
+        Self::with_source(
+            query,
+            Source::Keyset {
+                column: column.to_string(),
+                order,
+                last: None,
+            },
+        )
+    }
+
+    /// Streams the query through a cursor on the host.
+    ///
+    /// The cursor is opened on the first fetch, and closed when the rows run
+    /// out or the stream is dropped.
+    #[cfg(feature = "host-sql-cursor")]
+    pub fn cursor(query: DBQuery) -> Self {
+        Self::with_source(query, Source::Cursor { id: None })
+    }
+
+    /// Sets the maximum number of rows fetched in one page.
+    pub fn page_size(mut self, size: u32) -> Self {
+        self.page_size = size;
+        self
+    }
+
+    /// Decodes each row into `T`, see [`Row::decode`].
+    pub fn decode<T: DeserializeOwned>(self) -> impl Iterator<Item = Result<T>> {
+        self.map(|row| row.and_then(|row| row.decode()))
+    }
+
+    fn fetch(&mut self) -> Result<()> {
+        if self.page_size == 0 {
+            bail!("the page size of the stream should be positive");
+        }
+        #[cfg(feature = "host-sql-cursor")]
+        if let Source::Cursor { .. } = self.source {
+            return self.fetch_cursor();
+        }
+        let rows = self
+            .source
+            .page_query(&self.query, self.page_size)?
+            .query_rows()?;
+        if rows.len() < self.page_size as usize {
+            self.done = true;
+        }
+        match &mut self.source {
+            Source::Offset { offset } => *offset += rows.len() as u64,
+            Source::Keyset { column, last, .. } => {
+                if let Some(row) = rows.iter().last() {
+                    match row.get(column) {
+                        Some(v) if v.is_null() => {
+                            bail!("the key column `{}` of the stream is NULL", column)
+                        }
+                        Some(v) => *last = Some(Box::new(v.get_param())),
+                        None => bail!("no key column `{}` in the rows of the stream", column),
+                    }
+                }
+            }
+            #[cfg(feature = "host-sql-cursor")]
+            Source::Cursor { .. } => unreachable!(),
+        }
+        self.buffer.extend(rows);
+        Ok(())
+    }
+
+    #[cfg(feature = "host-sql-cursor")]
+    fn fetch_cursor(&mut self) -> Result<()> {
+        let id = match self.source {
+            Source::Cursor { id: Some(id) } => id,
+            _ => {
+                let encoded = self.query.encode()?;
+                let id = &mut 0_i32 as *const i32;
+                match unsafe { ws_sql_cursor_open(encoded.as_ptr(), encoded.len() as _, id) } {
+                    0 => {}
+                    _ => bail!("fail to open the sql cursor"),
+                }
+                let id = unsafe { *id };
+                self.source = Source::Cursor { id: Some(id) };
+                id
+            }
+        };
+        let data_ptr = &mut 0_i32 as *const _ as *const *mut u8;
+        let data_size = &mut 0_i32 as *const i32;
+        let ret = match unsafe { ws_sql_cursor_next(id, self.page_size as _, data_ptr, data_size) }
+        {
+            0 => unsafe { Vec::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) },
+            _ => bail!("fail to fetch from the sql cursor"),
+        };
+        let rows = super::Rows::from_slice(&ret)?;
+        if rows.is_empty() {
+            self.done = true;
+            self.close();
+        }
+        self.buffer.extend(rows);
+        Ok(())
+    }
+
+    #[cfg(feature = "host-sql-cursor")]
+    fn close(&mut self) {
+        if let Source::Cursor { id: Some(id) } = self.source {
+            unsafe { ws_sql_cursor_close(id) };
+            self.source = Source::Cursor { id: None };
+        }
+    }
+}
+
+#[cfg(feature = "host-sql-cursor")]
+impl Drop for QueryStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Iterator for QueryStream {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.buffer.is_empty() && !self.done {
+            if let Err(err) = self.fetch() {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+impl DBQuery {
+    /// Streams the query, see [`query_stream`].
+    pub fn stream(self) -> QueryStream {
+        #[cfg(feature = "host-sql-cursor")]
+        let stream = QueryStream::cursor(self);
+        #[cfg(not(feature = "host-sql-cursor"))]
+        let stream = QueryStream::offset(self);
+        stream
+    }
+}
+
+/// Query a prepared statement with parameters and iterate over the rows page by page.
+///
+/// It reads from a cursor on the host if the `host-sql-cursor` feature is
+/// enabled, and uses `LIMIT`/`OFFSET` pagination otherwise, see [`QueryStream`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::query_stream;
+/// for row in query_stream("SELECT id, value FROM readings ORDER BY id", &[]) {
+///     let value: f64 = row?.get_as("value")?;
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn query_stream(prepared: &str, params: &[&dyn SQLType]) -> QueryStream {
+    DBQuery::new(prepared, params).stream()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_query() {
+        let query = DBQuery::new("SELECT * FROM t WHERE a = ? ORDER BY id", &[&1]);
+        let q = Source::Offset { offset: 20 }
+            .page_query(&query, 10)
+            .unwrap();
+        assert_eq!(
+            q.statement,
+            "SELECT * FROM t WHERE a = ? ORDER BY id LIMIT ? OFFSET ?"
+        );
+        assert_eq!(
+            q.params,
+            vec![1.get_param(), 10_i64.get_param(), 20_i64.get_param()]
+        );
+        assert!(q.check().is_ok());
+
+        let mut keyset = Source::Keyset {
+            column: "id".to_string(),
+            order: Order::Desc,
+            last: None,
+        };
+        let q = keyset.page_query(&query, 10).unwrap();
+        assert_eq!(
+            q.statement,
+            "SELECT * FROM (SELECT * FROM t WHERE a = ? ORDER BY id) AS ws_page ORDER BY \"id\" DESC LIMIT ?"
+        );
+        if let Source::Keyset { last, .. } = &mut keyset {
+            *last = Some(Box::new(5_i64.get_param()));
+        }
+        let q = keyset.page_query(&query, 10).unwrap();
+        assert_eq!(
+            q.statement,
+            "SELECT * FROM (SELECT * FROM t WHERE a = ? ORDER BY id) AS ws_page WHERE \"id\" < ? ORDER BY \"id\" DESC LIMIT ?"
+        );
+        assert_eq!(q.params.len(), 3);
+        assert!(q.check().is_ok());
+    }
+}
@@ -0,0 +1,244 @@
+use super::{DBQuery, Rows, SQLType};
+use crate::host::abi::*;
+use anyhow::{bail, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Batch<'a> {
+    statements: &'a [DBQuery],
+}
+
+/// A SQL transaction on the host.
+///
+/// The statements run through the transaction are invisible to others until
+/// [`Transaction::commit`]. Dropping a transaction without committing rolls
+/// it back. It's usually used through [`transaction`].
+pub struct Transaction {
+    id: i32,
+    finished: bool,
+}
+
+/// Starts a transaction.
+pub fn begin() -> Result<Transaction> {
+    let id = &mut 0_i32 as *const i32;
+    match unsafe { ws_sql_begin(id) } {
+        0 => Ok(Transaction {
+            id: unsafe { *id },
+            finished: false,
+        }),
+        _ => bail!("fail to begin the transaction"),
+    }
+}
+
+impl Transaction {
+    /// Execute a prepared statement with parameters in the transaction.
+    pub fn execute(&self, prepared: &str, params: &[&dyn SQLType]) -> Result<()> {
+        self.execute_query(&DBQuery::new(prepared, params))
+    }
+
+    /// Execute a query, e.g. built by the [`builder`](super::builder), in the transaction.
+    pub fn execute_query(&self, query: &DBQuery) -> Result<()> {
+        let encoded = &query.encode()?;
+        match unsafe { ws_set_sql_db_tx(self.id, encoded.as_ptr(), encoded.len() as _) } {
+            0 => Ok(()),
+            _ => bail!("fail to exec sql in the transaction"),
+        }
+    }
+
+    /// Query a prepared statement with parameters in the transaction.
+    ///
+    /// The query result is returned in `json` format, see [`Rows`].
+    pub fn query(&self, prepared: &str, params: &[&dyn SQLType]) -> Result<Vec<u8>> {
+        self.fetch(&DBQuery::new(prepared, params))
+    }
+
+    /// Query a prepared statement in the transaction and decode the result into [`Rows`].
+    pub fn query_rows(&self, prepared: &str, params: &[&dyn SQLType]) -> Result<Rows> {
+        Rows::from_slice(&self.query(prepared, params)?)
+    }
+
+    /// Query a prepared statement in the transaction and decode each row into `T`.
+    pub fn query_as<T: DeserializeOwned>(
+        &self,
+        prepared: &str,
+        params: &[&dyn SQLType],
+    ) -> Result<Vec<T>> {
+        self.query_rows(prepared, params)?.decode()
+    }
+
+    /// Run a query, e.g. built by the [`builder`](super::builder), in the
+    /// transaction and return the result in `json` format.
+    pub fn fetch(&self, query: &DBQuery) -> Result<Vec<u8>> {
+        let encoded = &query.encode()?;
+        let data_ptr = &mut 0_i32 as *const _ as *const *mut u8;
+        let data_size = &mut 0_i32 as *const i32;
+        match unsafe {
+            ws_get_sql_db_tx(
+                self.id,
+                encoded.as_ptr(),
+                encoded.len() as _,
+                data_ptr,
+                data_size,
+            )
+        } {
+            0 => Ok(unsafe { Vec::from_raw_parts(*data_ptr, *data_size as _, *data_size as _) }),
+            _ => bail!("fail to query sql in the transaction"),
+        }
+    }
+
+    /// Commits the transaction.
+    pub fn commit(mut self) -> Result<()> {
+        self.finished = true;
+        match unsafe { ws_sql_commit(self.id) } {
+            0 => Ok(()),
+            _ => bail!("fail to commit the transaction"),
+        }
+    }
+
+    /// Rolls back the transaction.
+    pub fn rollback(mut self) -> Result<()> {
+        self.finished = true;
+        match unsafe { ws_sql_rollback(self.id) } {
+            0 => Ok(()),
+            _ => bail!("fail to rollback the transaction"),
+        }
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if !self.finished {
+            unsafe { ws_sql_rollback(self.id) };
+        }
+    }
+}
+
+/// Runs `f` in a transaction, which is committed if `f` returns `Ok` and
+/// rolled back otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::transaction;
+/// transaction(|tx| {
+///     tx.execute("UPDATE devices SET credit = credit - ? WHERE id = ?;", &[&10, &1])?;
+///     tx.execute("INSERT INTO rewards (device_id, amount) VALUES (?, ?);", &[&1, &10])?;
+///     Ok(())
+/// })?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn transaction<T>(f: impl FnOnce(&Transaction) -> Result<T>) -> Result<T> {
+    let tx = begin()?;
+    match f(&tx) {
+        Ok(ret) => {
+            tx.commit()?;
+            Ok(ret)
+        }
+        Err(err) => {
+            // the error of `f` is more useful than the one of rolling back.
+            let _ = tx.rollback();
+            Err(err)
+        }
+    }
+}
+
+/// Execute a list of statements in one host call.
+///
+/// The statements are executed in order and atomically: if any of them fails,
+/// none of them takes effect.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::{execute_batch, DBQuery};
+/// execute_batch(&[
+///     DBQuery::new("DELETE FROM readings WHERE device_id = ?;", &[&1]),
+///     DBQuery::new("UPDATE devices SET active = ? WHERE id = ?;", &[&false, &1]),
+/// ])?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn execute_batch(queries: &[DBQuery]) -> Result<()> {
+    if queries.is_empty() {
+        return Ok(());
+    }
+    let encoded = &encode_batch(queries)?;
+    match unsafe { ws_set_sql_db_batch(encoded.as_ptr(), encoded.len() as _) } {
+        0 => Ok(()),
+        _ => bail!("fail to exec the sql batch"),
+    }
+}
+
+fn encode_batch(queries: &[DBQuery]) -> Result<String> {
+    for query in queries {
+        query.check()?;
+    }
+    Ok(serde_json::to_string(&Batch {
+        statements: queries,
+    })?)
+}
+
+/// Execute a prepared statement once for each row of parameters in one host call.
+///
+/// It's executed atomically as [`execute_batch`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ws_sdk::database::sql::execute_many;
+/// execute_many(
+///     "INSERT INTO readings (device_id, value) VALUES (?, ?);",
+///     &[&[&1, &21.5], &[&2, &22.0]],
+/// )?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn execute_many(prepared: &str, rows: &[&[&dyn SQLType]]) -> Result<()> {
+    execute_batch(&repeat(prepared, rows))
+}
+
+fn repeat(prepared: &str, rows: &[&[&dyn SQLType]]) -> Vec<DBQuery> {
+    rows.iter().map(|r| DBQuery::new(prepared, r)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_encode_batch() {
+        let encoded = encode_batch(&[
+            DBQuery::new("DELETE FROM readings WHERE device_id = ?;", &[&1]),
+            DBQuery::new("UPDATE devices SET active = ? WHERE id = ?;", &[&false, &1]),
+        ])
+        .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&encoded).unwrap(),
+            json!({"statements": [
+                {"statement": "DELETE FROM readings WHERE device_id = ?;", "params": [{"int32": 1}]},
+                {"statement": "UPDATE devices SET active = ? WHERE id = ?;",
+                    "params": [{"bool": false}, {"int32": 1}]},
+            ]})
+        );
+        // a statement with mismatched parameters fails the whole batch
+        assert!(encode_batch(&[
+            DBQuery::new("SELECT 1;", &[]),
+            DBQuery::new("SELECT ?, ?;", &[&1]),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_repeat() {
+        let prepared = "INSERT INTO readings (device_id, value) VALUES (?, ?);";
+        let queries = repeat(prepared, &[&[&1, &21.5], &[&2, &22.0]]);
+        assert_eq!(
+            queries,
+            vec![
+                DBQuery::new(prepared, &[&1, &21.5]),
+                DBQuery::new(prepared, &[&2, &22.0]),
+            ]
+        );
+        assert!(repeat(prepared, &[]).is_empty());
+    }
+}
@@ -1,26 +1,38 @@
+use anyhow::{bail, Context, Result};
 use base64::{engine::general_purpose, Engine as _};
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::skip_serializing_none;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+/// A parameter of the statement, holding exactly one of its fields.
 #[skip_serializing_none]
-#[derive(Serialize, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Param {
     pub int32: Option<i32>,
     pub int64: Option<i64>,
+    pub uint64: Option<u64>,
     pub float32: Option<f32>,
     pub float64: Option<f64>,
     pub string: Option<String>,
-    // pub time: Option<String>, //  rfc3339 encoding
+    pub time: Option<String>, //  rfc3339 encoding
     pub bool: Option<bool>,
-    pub bytes: Option<String>, // base64 encoding
+    pub bytes: Option<String>,   // base64 encoding
+    pub decimal: Option<String>, // decimal string, e.g. "-12.345"
+    pub json: Option<String>,    // json encoding
+    pub null: Option<bool>,      // `true` for NULL
 }
 
-#[derive(Serialize)]
+/// A statement with its parameters bound to the `?` placeholders in order,
+/// which is sent to the host.
+#[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct DBQuery {
     pub statement: String,
     pub params: Vec<Param>,
 }
 
+/// A type which can be bound to a parameter of the statement.
 pub trait SQLType {
     fn get_param(&self) -> Param;
 }
@@ -87,3 +99,439 @@ impl SQLType for Vec<u8> {
         }
     }
 }
+
+impl SQLType for &[u8] {
+    fn get_param(&self) -> Param {
+        Param {
+            bytes: Some(general_purpose::STANDARD_NO_PAD.encode(self)),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLType for String {
+    fn get_param(&self) -> Param {
+        self.as_str().get_param()
+    }
+}
+
+impl SQLType for i8 {
+    fn get_param(&self) -> Param {
+        (*self as i32).get_param()
+    }
+}
+
+impl SQLType for i16 {
+    fn get_param(&self) -> Param {
+        (*self as i32).get_param()
+    }
+}
+
+impl SQLType for u8 {
+    fn get_param(&self) -> Param {
+        (*self as i32).get_param()
+    }
+}
+
+impl SQLType for u16 {
+    fn get_param(&self) -> Param {
+        (*self as i32).get_param()
+    }
+}
+
+impl SQLType for u32 {
+    fn get_param(&self) -> Param {
+        (*self as i64).get_param()
+    }
+}
+
+impl SQLType for u64 {
+    fn get_param(&self) -> Param {
+        Param {
+            uint64: Some(*self),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLType for i128 {
+    fn get_param(&self) -> Param {
+        Decimal::from(*self).get_param()
+    }
+}
+
+impl SQLType for u128 {
+    fn get_param(&self) -> Param {
+        Decimal::from(*self).get_param()
+    }
+}
+
+impl<T: SQLType> SQLType for Option<T> {
+    fn get_param(&self) -> Param {
+        match self {
+            Some(v) => v.get_param(),
+            None => Param {
+                null: Some(true),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl SQLType for serde_json::Value {
+    fn get_param(&self) -> Param {
+        Param {
+            json: Some(self.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLType for SystemTime {
+    fn get_param(&self) -> Param {
+        Timestamp::from(*self).get_param()
+    }
+}
+
+impl SQLType for Timestamp {
+    fn get_param(&self) -> Param {
+        Param {
+            time: Some(self.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLType for Decimal {
+    fn get_param(&self) -> Param {
+        Param {
+            decimal: Some(self.0.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+/// A point in time, encoded in [RFC 3339] format in UTC.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::database::sql::Timestamp;
+/// let t: Timestamp = "2023-04-01T12:30:00.5+08:00".parse()?;
+/// assert_eq!(t.to_string(), "2023-04-01T04:30:00.5Z");
+/// assert_eq!(t.unix_timestamp(), 1680323400);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp {
+    secs: i64,
+    nanos: u32,
+}
+
+impl Timestamp {
+    /// Creates a timestamp from the seconds since the unix epoch.
+    pub fn from_unix(secs: i64) -> Self {
+        Timestamp { secs, nanos: 0 }
+    }
+
+    /// Returns the seconds since the unix epoch.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.secs
+    }
+
+    /// Returns the nanoseconds part of the timestamp.
+    pub fn subsec_nanos(&self) -> u32 {
+        self.nanos
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(t: SystemTime) -> Self {
+        match t.duration_since(UNIX_EPOCH) {
+            Ok(d) => Timestamp {
+                secs: d.as_secs() as _,
+                nanos: d.subsec_nanos(),
+            },
+            Err(e) => {
+                let d = e.duration();
+                let (secs, nanos) = (d.as_secs() as i64, d.subsec_nanos());
+                match nanos {
+                    0 => Timestamp::from_unix(-secs),
+                    _ => Timestamp {
+                        secs: -secs - 1,
+                        nanos: 1_000_000_000 - nanos,
+                    },
+                }
+            }
+        }
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(t: Timestamp) -> Self {
+        let nanos = Duration::from_nanos(t.nanos as _);
+        match t.secs {
+            s if s >= 0 => UNIX_EPOCH + Duration::from_secs(s as _) + nanos,
+            s => UNIX_EPOCH - Duration::from_secs(s.unsigned_abs()) + nanos,
+        }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.secs.div_euclid(86400);
+        let secs = self.secs.rem_euclid(86400);
+        let (y, m, d) = civil_from_days(days);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            y,
+            m,
+            d,
+            secs / 3600,
+            secs % 3600 / 60,
+            secs % 60
+        )?;
+        if self.nanos > 0 {
+            let frac = format!("{:09}", self.nanos);
+            write!(f, ".{}", frac.trim_end_matches('0'))?;
+        }
+        write!(f, "Z")
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_rfc3339(s).with_context(|| format!("invalid rfc3339 time `{}`", s))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+fn parse_rfc3339(s: &str) -> Result<Timestamp> {
+    if !s.is_ascii() {
+        bail!("malformed date time");
+    }
+    let b = s.as_bytes();
+    if b.len() < 20 || b[4] != b'-' || b[7] != b'-' || b[13] != b':' || b[16] != b':' {
+        bail!("malformed date time");
+    }
+    if !matches!(b[10], b'T' | b't' | b' ') {
+        bail!("malformed date time separator");
+    }
+    let num = |from: usize, to: usize| -> Result<i64> {
+        let part = &s[from..to];
+        if !part.bytes().all(|c| c.is_ascii_digit()) {
+            bail!("malformed number `{}`", part);
+        }
+        Ok(part.parse()?)
+    };
+    let (y, m, d) = (num(0, 4)?, num(5, 7)?, num(8, 10)?);
+    let (hh, mm, ss) = (num(11, 13)?, num(14, 16)?, num(17, 19)?);
+    if !(1..=12).contains(&m) || d < 1 || d > days_in_month(y, m) {
+        bail!("date out of range");
+    }
+    if hh > 23 || mm > 59 || ss > 60 {
+        bail!("time out of range");
+    }
+
+    let mut rest = &s[19..];
+    let mut nanos = 0u32;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let len = frac.bytes().take_while(|c| c.is_ascii_digit()).count();
+        if len == 0 {
+            bail!("malformed fraction of second");
+        }
+        let digits = &frac[..len.min(9)];
+        nanos = digits.parse::<u32>()? * 10u32.pow(9 - digits.len() as u32);
+        rest = &frac[len..];
+    }
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ if rest.len() == 6 && matches!(&rest[..1], "+" | "-") && &rest[3..4] == ":" => {
+            let (oh, om) = (num(s.len() - 5, s.len() - 3)?, num(s.len() - 2, s.len())?);
+            if oh > 23 || om > 59 {
+                bail!("offset out of range");
+            }
+            let offset = oh * 3600 + om * 60;
+            if rest.starts_with('-') {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => bail!("malformed offset"),
+    };
+    Ok(Timestamp {
+        secs: days_from_civil(y, m, d) * 86400 + hh * 3600 + mm * 60 + ss.min(59) - offset,
+        nanos,
+    })
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    match m {
+        2 if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// The conversions between days since the unix epoch and the civil date, see
+// http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+/// An arbitrary precision decimal number, e.g. a token balance.
+///
+/// It's sent to the host as a decimal string, so no precision is lost.
+///
+/// # Examples
+///
+/// ```
+/// use ws_sdk::database::sql::Decimal;
+/// let wei = Decimal::from(1_000_000_000_000_000_000u128);
+/// let price: Decimal = "-12.345".parse()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Decimal(String);
+
+impl Decimal {
+    /// Returns the decimal string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s.strip_prefix('-').unwrap_or(s);
+        let (int, frac) = match digits.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (digits, None),
+        };
+        let is_digits = |p: &str| !p.is_empty() && p.bytes().all(|c| c.is_ascii_digit());
+        if !is_digits(int) || matches!(frac, Some(f) if !is_digits(f)) {
+            bail!("invalid decimal `{}`", s);
+        }
+        Ok(Decimal(s.to_string()))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<u128> for Decimal {
+    fn from(v: u128) -> Self {
+        Decimal(v.to_string())
+    }
+}
+
+impl From<i128> for Decimal {
+    fn from(v: i128) -> Self {
+        Decimal(v.to_string())
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(v: u64) -> Self {
+        Decimal(v.to_string())
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(v: i64) -> Self {
+        Decimal(v.to_string())
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp() {
+        let t: Timestamp = "1970-01-01T00:00:00Z".parse().unwrap();
+        assert_eq!(t, Timestamp::from_unix(0));
+
+        let t: Timestamp = "2024-02-29T23:59:59.123-01:00".parse().unwrap();
+        assert_eq!(t.unix_timestamp(), 1709254799);
+        assert_eq!(t.subsec_nanos(), 123_000_000);
+        assert_eq!(t.to_string(), "2024-03-01T00:59:59.123Z");
+
+        let t = Timestamp::from(UNIX_EPOCH - Duration::from_millis(1500));
+        assert_eq!(t.to_string(), "1969-12-31T23:59:58.5Z");
+        assert_eq!(
+            SystemTime::from(t),
+            UNIX_EPOCH - Duration::from_millis(1500)
+        );
+
+        assert!("2023-02-29T00:00:00Z".parse::<Timestamp>().is_err());
+        assert!("2023-01-01T00:00:00".parse::<Timestamp>().is_err());
+        assert!("2023-04-01T00:00:0éZ".parse::<Timestamp>().is_err());
+        assert!("2023-04-01T00:00:00.1é".parse::<Timestamp>().is_err());
+        assert!("2023-04-01T00:00:00+0é:00".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert!("123".parse::<Decimal>().is_ok());
+        assert!("-0.001".parse::<Decimal>().is_ok());
+        assert!("1.".parse::<Decimal>().is_err());
+        assert!("1e18".parse::<Decimal>().is_err());
+        assert_eq!(Decimal::from(u128::MAX).as_str(), u128::MAX.to_string());
+    }
+}
@@ -25,6 +25,36 @@ extern "C" {
         value_ptr: *const u8,
         value_size: i32,
     ) -> i32;
+    #[cfg(feature = "host-ttl")]
+    pub fn ws_set_db_with_ttl(
+        key_ptr: *const u8,
+        key_size: i32,
+        value_ptr: *const u8,
+        value_size: i32,
+        ttl_secs: i64,
+    ) -> i32;
+    pub fn ws_del_db(key_ptr: *const u8, key_size: i32) -> i32;
+    pub fn ws_incr_db(
+        key_ptr: *const u8,
+        key_size: i32,
+        delta: i64,
+        return_value: *const i64,
+    ) -> i32;
+    pub fn ws_cas_db(
+        key_ptr: *const u8,
+        key_size: i32,
+        expected_ptr: *const u8,
+        expected_size: i32,
+        new_ptr: *const u8,
+        new_size: i32,
+    ) -> i32;
+    pub fn ws_write_db_batch(ptr: *const u8, size: i32) -> i32;
+    pub fn ws_scan_db(
+        ptr: *const u8,
+        size: i32,
+        return_ptr: *const *mut u8,
+        return_size: *const i32,
+    ) -> i32;
     pub fn ws_get_sql_db(
         ptr: *const u8,
         size: i32,
@@ -32,6 +62,29 @@ extern "C" {
         return_size: *const i32,
     ) -> i32;
     pub fn ws_set_sql_db(ptr: *const u8, size: i32) -> i32;
+    pub fn ws_set_sql_db_batch(ptr: *const u8, size: i32) -> i32;
+    pub fn ws_sql_begin(return_tx: *const i32) -> i32;
+    pub fn ws_sql_commit(tx: i32) -> i32;
+    pub fn ws_sql_rollback(tx: i32) -> i32;
+    pub fn ws_get_sql_db_tx(
+        tx: i32,
+        ptr: *const u8,
+        size: i32,
+        return_ptr: *const *mut u8,
+        return_size: *const i32,
+    ) -> i32;
+    pub fn ws_set_sql_db_tx(tx: i32, ptr: *const u8, size: i32) -> i32;
+    #[cfg(feature = "host-sql-cursor")]
+    pub fn ws_sql_cursor_open(ptr: *const u8, size: i32, return_cursor: *const i32) -> i32;
+    #[cfg(feature = "host-sql-cursor")]
+    pub fn ws_sql_cursor_next(
+        cursor: i32,
+        max_rows: i32,
+        return_ptr: *const *mut u8,
+        return_size: *const i32,
+    ) -> i32;
+    #[cfg(feature = "host-sql-cursor")]
+    pub fn ws_sql_cursor_close(cursor: i32) -> i32;
     pub fn ws_send_tx(
         chain_id: i32,
         payload_ptr: *const u8,
@@ -39,6 +92,14 @@ extern "C" {
         return_hash_ptr: *const *mut u8,
         return_hash_size: *const i32,
     ) -> i32;
+    #[cfg(feature = "host-raw-tx")]
+    pub fn ws_send_raw_tx(
+        chain_id: i32,
+        raw_ptr: *const u8,
+        raw_size: i32,
+        return_hash_ptr: *const *mut u8,
+        return_hash_size: *const i32,
+    ) -> i32;
     pub fn ws_call_contract(
         chain_id: i32,
         ptr: *const u8,
@@ -47,3 +47,8 @@ mod host;
 pub mod log;
 pub mod metrics;
 pub mod stream;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use anyhow;
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "abi")]
+
+use ws_sdk::blockchain::abi::{abigen, param_types, Address, Token, U256};
+
+abigen!(SetGet, "tests/abi/set_get.json");
+
+abigen!(
+    Registry,
+    r#"[
+        {
+            "type": "function",
+            "name": "register",
+            "stateMutability": "payable",
+            "inputs": [
+                { "name": "owner", "type": "address" },
+                {
+                    "name": "devices",
+                    "type": "tuple[]",
+                    "components": [
+                        { "name": "id", "type": "bytes32" },
+                        { "name": "weight", "type": "int16" }
+                    ]
+                }
+            ],
+            "outputs": []
+        },
+        {
+            "type": "function",
+            "name": "deviceOf",
+            "stateMutability": "view",
+            "inputs": [{ "name": "id", "type": "bytes32" }],
+            "outputs": [
+                { "name": "owner", "type": "address" },
+                { "name": "active", "type": "bool" }
+            ]
+        },
+        { "type": "event", "name": "Registered", "inputs": [], "anonymous": false }
+    ]"#
+);
+
+abigen!(
+    Clashes,
+    r#"[
+        {
+            "type": "function",
+            "name": "deposit",
+            "stateMutability": "payable",
+            "inputs": [{ "name": "tx_value", "type": "uint256" }],
+            "outputs": []
+        },
+        {
+            "type": "function",
+            "name": "encodeRoot",
+            "stateMutability": "pure",
+            "inputs": [],
+            "outputs": [{ "name": "", "type": "bytes32" }]
+        },
+        {
+            "type": "function",
+            "name": "root",
+            "stateMutability": "view",
+            "inputs": [],
+            "outputs": [{ "name": "", "type": "bytes32" }]
+        },
+        {
+            "type": "function",
+            "name": "get",
+            "stateMutability": "view",
+            "inputs": [],
+            "outputs": [{ "name": "", "type": "uint256" }]
+        },
+        {
+            "type": "function",
+            "name": "get",
+            "stateMutability": "view",
+            "inputs": [{ "name": "key", "type": "uint256" }],
+            "outputs": [{ "name": "", "type": "uint256" }]
+        }
+    ]"#
+);
+
+#[test]
+fn test_encode() {
+    let data = SetGet::encode_set(U256::from(42u64)).unwrap();
+    let mut expected = vec![0x60, 0xfe, 0x47, 0xb1];
+    expected.extend(ethabi::encode(&[Token::Uint(42.into())]));
+    assert_eq!(data, expected);
+    assert_eq!(SetGet::encode_get().unwrap(), vec![0x6d, 0x4c, 0xe6, 0x3c]);
+
+    let addr: Address = "0x3908c0620ABC34a23A078097e2e878AFf60bbC28"
+        .parse()
+        .unwrap();
+    let contract = SetGet::new(4690, addr);
+    assert_eq!(contract.chain_id(), 4690);
+    assert_eq!(contract.address(), addr);
+
+    let owner = Address::from([0x11; 20]);
+    let data = Registry::encode_register(owner, vec![([7; 32], -1)]).unwrap();
+    let types = param_types(&["address", "(bytes32,int16)[]"]).unwrap();
+    assert_eq!(data[..4], ethabi::short_signature("register", &types));
+    assert_eq!(
+        ethabi::decode(&types, &data[4..]).unwrap(),
+        vec![
+            Token::Address(ethabi::Address::repeat_byte(0x11)),
+            Token::Array(vec![Token::Tuple(vec![
+                Token::FixedBytes(vec![7; 32]),
+                Token::Int(ethabi::Uint::MAX),
+            ])]),
+        ]
+    );
+    assert_eq!(Registry::encode_device_of([0; 32]).unwrap().len(), 36);
+}
+
+#[test]
+fn test_clashes() {
+    let selector = |name: &str, types: &[&str]| {
+        ethabi::short_signature(name, &param_types(types).unwrap()).to_vec()
+    };
+    // the argument `tx_value` is renamed after the value of the transaction
+    let data = Clashes::encode_deposit(U256::from(1u64)).unwrap();
+    assert_eq!(data[..4], selector("deposit", &["uint256"]));
+    // `encodeRoot` doesn't shadow the helper of `root`
+    assert_eq!(Clashes::encode_root().unwrap(), selector("root", &[]));
+    assert_eq!(
+        Clashes::encode_encode_root_().unwrap(),
+        selector("encodeRoot", &[])
+    );
+    // the first overload keeps the bare name
+    assert_eq!(Clashes::encode_get().unwrap(), selector("get", &[]));
+    let data = Clashes::encode_get_1(U256::ONE).unwrap();
+    assert_eq!(data[..4], selector("get", &["uint256"]));
+}
@@ -0,0 +1,183 @@
+#![cfg(all(feature = "crypto", feature = "derive"))]
+
+use ws_sdk::blockchain::types::{keccak256, Address, H256, U256};
+use ws_sdk::crypto::eip712::{self, Domain, Eip712, TypedData};
+
+#[derive(Eip712)]
+struct Person {
+    name: String,
+    wallet: Address,
+}
+
+#[derive(Eip712)]
+struct Mail {
+    from: Person,
+    to: Person,
+    contents: String,
+}
+
+#[derive(Eip712)]
+#[eip712(name = "Attestation")]
+struct DeviceAttestation {
+    #[eip712(rename = "deviceId")]
+    device_id: H256,
+    readings: Vec<i64>,
+    reward: U256,
+    signers: [Address; 2],
+}
+
+fn mail() -> Mail {
+    Mail {
+        from: Person {
+            name: "Cow".into(),
+            wallet: "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                .parse()
+                .unwrap(),
+        },
+        to: Person {
+            name: "Bob".into(),
+            wallet: "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+                .parse()
+                .unwrap(),
+        },
+        contents: "Hello, Bob!".into(),
+    }
+}
+
+fn domain() -> Domain {
+    Domain::new(
+        "Ether Mail",
+        "1",
+        1,
+        "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            .parse()
+            .unwrap(),
+    )
+}
+
+#[test]
+fn derive_eip712() {
+    assert_eq!(
+        Mail::encode_type(),
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+    let mail = mail();
+    assert_eq!(
+        mail.struct_hash().to_string(),
+        "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+    let digest = mail.digest(&domain());
+    assert_eq!(
+        digest.to_string(),
+        "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+
+    let key = hex::encode(keccak256(b"cow"));
+    let sig = eip712::sign(&key, &digest).unwrap();
+    assert!(eip712::verify(&digest, &sig, mail.from.wallet).is_ok());
+    assert!(eip712::verify(&digest, &sig, mail.to.wallet).is_err());
+
+    assert_eq!(
+        DeviceAttestation::encode_type(),
+        "Attestation(bytes32 deviceId,int64[] readings,uint256 reward,address[2] signers)"
+    );
+}
+
+#[test]
+fn derive_eip712_matches_typed_data() {
+    let attestation = DeviceAttestation {
+        device_id: H256::from([0x42; 32]),
+        readings: vec![-3, 7],
+        reward: U256::from_ether("1.5").unwrap(),
+        signers: [Address::from([0x11; 20]), Address::from([0x22; 20])],
+    };
+    let data = TypedData::from_json(&format!(
+        r#"{{
+            "types": {{
+                "Attestation": [
+                    {{"name": "deviceId", "type": "bytes32"}},
+                    {{"name": "readings", "type": "int64[]"}},
+                    {{"name": "reward", "type": "uint256"}},
+                    {{"name": "signers", "type": "address[2]"}}
+                ]
+            }},
+            "primaryType": "Attestation",
+            "domain": {{"name": "Attestor", "chainId": "4690"}},
+            "message": {{
+                "deviceId": "{}",
+                "readings": [-3, "7"],
+                "reward": "1500000000000000000",
+                "signers": ["{}", "{}"]
+            }}
+        }}"#,
+        attestation.device_id, attestation.signers[0], attestation.signers[1]
+    ))
+    .unwrap();
+    assert_eq!(
+        data.encode_type().unwrap(),
+        DeviceAttestation::encode_type()
+    );
+    assert_eq!(data.struct_hash().unwrap(), attestation.struct_hash());
+
+    let domain = Domain {
+        name: Some("Attestor".into()),
+        chain_id: Some(U256::from(4690u64)),
+        ..Default::default()
+    };
+    assert_eq!(data.digest().unwrap(), attestation.digest(&domain));
+}
+
+#[cfg(feature = "abi")]
+#[test]
+fn typed_data_calldata() {
+    use ws_sdk::blockchain::abi::{encode_call, Token};
+
+    let data = TypedData::from_json(
+        r#"{
+            "types": {
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"}
+                ],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {},
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        }"#,
+    )
+    .unwrap();
+    let key = hex::encode(keccak256(b"cow"));
+    let sig = eip712::sign(&key, &data.digest().unwrap()).unwrap();
+    let calldata = data.calldata("send", &sig).unwrap();
+
+    let mail = mail();
+    let person = |p: &Person| {
+        Token::Tuple(vec![
+            Token::String(p.name.clone()),
+            Token::Address(p.wallet.as_bytes().into()),
+        ])
+    };
+    let expected = encode_call(
+        "send",
+        &["((string,address),(string,address),string)", "bytes"],
+        vec![
+            Token::Tuple(vec![
+                person(&mail.from),
+                person(&mail.to),
+                Token::String(mail.contents.clone()),
+            ]),
+            Token::Bytes(hex::decode(&sig).unwrap()),
+        ],
+    )
+    .unwrap();
+    assert_eq!(calldata, expected);
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "abi")]
+
+use ws_sdk::blockchain::abi::Token;
+use ws_sdk::blockchain::events::{event_topic, parse_log, EthEvent};
+use ws_sdk::blockchain::types::{keccak256, Address, Log, H256, U256};
+
+#[derive(EthEvent, Debug, PartialEq)]
+struct Transfer {
+    #[ethevent(indexed)]
+    from: Address,
+    #[ethevent(indexed)]
+    to: Address,
+    value: U256,
+}
+
+#[derive(EthEvent, Debug, PartialEq)]
+#[ethevent(name = "DeviceRegistered")]
+struct Registered {
+    #[ethevent(indexed, abi = "string")]
+    id: H256,
+    owner: Address,
+    tags: Vec<String>,
+}
+
+fn topic(addr: Address) -> H256 {
+    let mut topic = [0; 32];
+    topic[12..].copy_from_slice(addr.as_bytes());
+    H256::from(topic)
+}
+
+#[test]
+fn test_eth_event() {
+    assert_eq!(Transfer::signature(), "Transfer(address,address,uint256)");
+    assert_eq!(
+        Registered::signature(),
+        "DeviceRegistered(string,address,string[])"
+    );
+
+    let (from, to) = (Address::from([1; 20]), Address::from([2; 20]));
+    let log = parse_log(
+        format!(
+            r#"{{"address":"{}","topics":["{}","{}","{}"],"data":"0x{}"}}"#,
+            Address::ZERO,
+            Transfer::topic(),
+            topic(from),
+            topic(to),
+            hex::encode(U256::from(1000u64).to_big_endian())
+        )
+        .as_bytes(),
+    )
+    .unwrap();
+    assert!(Transfer::matches(&log));
+    assert!(!Registered::matches(&log));
+    assert_eq!(
+        Transfer::decode_log(&log).unwrap(),
+        Transfer {
+            from,
+            to,
+            value: U256::from(1000u64)
+        }
+    );
+    assert!(Registered::decode_log(&log).is_err());
+
+    let id = H256::from(keccak256(b"device-1"));
+    let log = Log {
+        topics: vec![event_topic("DeviceRegistered(string,address,string[])"), id],
+        data: ethabi::encode(&[
+            Token::Address(ethabi::Address::repeat_byte(1)),
+            Token::Array(vec![Token::String("a".into())]),
+        ]),
+        ..log
+    };
+    assert_eq!(
+        Registered::decode_log(&log).unwrap(),
+        Registered {
+            id,
+            owner: from,
+            tags: vec!["a".into()]
+        }
+    );
+}
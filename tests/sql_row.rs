@@ -0,0 +1,62 @@
+#![cfg(feature = "derive")]
+
+use ws_sdk::database::sql::{Decimal, Rows, SQLType, SqlRow};
+
+#[derive(SqlRow, Debug, PartialEq)]
+#[sql(table = "readings")]
+struct Reading {
+    #[sql(rename = "device_id")]
+    device: String,
+    value: f64,
+    balance: Option<Decimal>,
+    #[sql(skip)]
+    cached: bool,
+}
+
+#[derive(SqlRow)]
+struct DeviceStatus {
+    id: u64,
+}
+
+#[test]
+fn test_derive_sql_row() {
+    assert_eq!(DeviceStatus::TABLE, "device_status");
+    assert_eq!(Reading::COLUMNS, ["device_id", "value", "balance"]);
+
+    let reading = Reading {
+        device: "dev-1".to_string(),
+        value: 21.5,
+        balance: None,
+        cached: true,
+    };
+    let query = reading.insert().unwrap();
+    assert_eq!(
+        query.statement,
+        r#"INSERT INTO "readings" ("device_id", "value", "balance") VALUES (?, ?, ?);"#
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            "dev-1".get_param(),
+            21.5.get_param(),
+            None::<Decimal>.get_param()
+        ]
+    );
+
+    let rows = Rows::from_slice(
+        br#"{
+            "columns": ["device_id", "value", "balance"],
+            "rows": [[{"string": "dev-1"}, {"float64": 21.5}, {"decimal": "100"}]]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Reading::from_rows(&rows).unwrap(),
+        vec![Reading {
+            device: "dev-1".to_string(),
+            value: 21.5,
+            balance: Some(Decimal::from(100u64)),
+            cached: false,
+        }]
+    );
+}